@@ -0,0 +1,70 @@
+//! Time ticking a sparse, a dense and a growing board: serially, in threads and packed.
+//!
+//! `cargo bench`, or `cargo bench -- <filter>` to only run the rows whose name contains it.
+
+use cgol_tui::app::{shapes, Area, PackedUniverse, Universe};
+use std::{
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// How long to keep ticking a board
+const BUDGET: Duration = Duration::from_secs(2);
+
+/// The boards to tick: sparse, dense and a growing one
+fn boards() -> Vec<Universe> {
+    let glider = Universe::from_str(shapes::GLIDER).expect("valid pattern");
+    let gun = Universe::from_str(shapes::GOSPER_GLIDER_GUN).expect("valid pattern");
+    vec![
+        Universe::from_figur(Area::new(512, 512), glider)
+            .expect("fits")
+            .with_name("glider in 512x512"),
+        shapes::rand_seeded(Area::new(256, 256), 0).with_name("random 256x256"),
+        Universe::from_figur(Area::new(128, 128), gun)
+            .expect("fits")
+            .with_name("gosper glider gun in 128x128"),
+    ]
+}
+
+/// Call `tick` for `BUDGET`, print how long one call took on average
+fn row(filter: Option<&str>, name: &str, mut tick: impl FnMut()) {
+    if filter.is_some_and(|filter| !name.contains(filter)) {
+        return;
+    }
+    let start = Instant::now();
+    let mut ticks = 0_u32;
+    while start.elapsed() < BUDGET {
+        tick();
+        ticks += 1;
+    }
+    let per_tick = start.elapsed() / ticks;
+    println!("{name:<40} {ticks:>8} {:>12}", format!("{per_tick:.2?}"));
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, anything else is a filter
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let filter = filter.as_deref();
+    let threads = std::thread::available_parallelism().map_or(1, usize::from);
+
+    println!("{:<40} {:>8} {:>12}", "board", "ticks", "per tick");
+    for board in boards() {
+        let mut univ = board.clone();
+        row(filter, board.name(), || {
+            black_box(univ.tick());
+        });
+        let mut univ = board.clone();
+        row(
+            filter,
+            &format!("{}, {threads} threads", board.name()),
+            || {
+                black_box(univ.tick_in_threads(threads));
+            },
+        );
+        let mut packed = PackedUniverse::try_from(&board).expect("packs");
+        row(filter, &format!("{}, packed", board.name()), || {
+            black_box(packed.tick());
+        });
+    }
+}
@@ -1,19 +1,79 @@
 pub use area::Area;
 pub use cell::Cell;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use ratatui::{backend::Backend, Terminal};
-use std::{io, str::FromStr, time::Duration};
-pub use universe::Universe;
+pub use command::{parse_coords, Command};
+pub use config::Config;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+pub use glyphs::Glyphs;
+use ratatui::{backend::Backend, layout::Rect, Terminal};
+pub use rule::{RegionRule, Rule};
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+pub use trace::Trace;
+pub use ui::Watermark;
+pub use universe::{
+    BoundaryMode, Longevity, Overlap, PackedUniverse, TickOutcome, Universe, UniverseBuilder,
+};
 
 /// Default poll duration
 const DEF_DUR: Duration = Duration::from_millis(400);
+/// Shortest poll duration reachable with `App::faster`
+const MIN_POLL: Duration = Duration::from_millis(10);
+/// Longest poll duration reachable with `App::slower`
+const MAX_POLL: Duration = Duration::from_secs(4);
 /// Pause duration: a day
 const PAUSE: Duration = Duration::from_secs(60 * 60 * 24);
+/// Generations to go slower for after an event, see `App::auto_slow`
+const SLOW_GENS: u8 = 8;
+/// Generations to flash the border for after an event, see `App::flash_on_event`
+const FLASH_GENS: u8 = 3;
+/// How many times slower to go after an event
+const SLOW_FACTOR: u32 = 4;
+/// Generations to wait before reseeding a settled board, see `App::auto_restart`
+const RESTART_DELAY: u8 = 16;
+/// Generations a message stays in the footer for
+const MESSAGE_GENS: u8 = 16;
+/// Most generations `:skip` goes through
+const SKIP_MAX: u64 = 10_000;
+/// Number of entries kept in `App::log`
+const LOG_LEN: usize = 8;
+/// Default number of cells changing in one generation to count as an event
+const DEF_EVENT_THRESHOLD: usize = 32;
+/// Number of previous states kept in `App::history`, and undone ones in `App::redo`
+const HISTORY_LEN: usize = 256;
+/// Number of `Universe::state_hash`es kept in `App::recent_hashes`:
+/// cycles with a longer period go unnoticed
+const CYCLE_WINDOW: usize = 64;
 
 mod area;
 mod cell;
+/// CRC-32 for gzip and PNG
+#[cfg(any(feature = "gzip", feature = "image"))]
+mod checksum;
+/// Commands typed after `:`
+mod command;
+/// Experiment setup files
+mod config;
+/// Symbols for drawing cells as text
+mod glyphs;
+/// Reading gzipped patterns
+#[cfg(feature = "gzip")]
+mod gzip;
+/// Writing PNG images
+#[cfg(feature = "image")]
+mod png;
+/// Birth/survival rules
+mod rule;
 /// Starting shapes
 pub mod shapes;
+/// Following a spaceship
+mod trace;
 /// ui
 mod ui;
 /// Conway's Game of Life universe
@@ -22,12 +82,85 @@ mod universe;
 #[cfg(test)]
 mod tests;
 
+/// Callback observing every generation, see `App::tick_hook`
+pub type TickHook = Box<dyn FnMut(&Universe)>;
+
 pub struct App {
     pub available_universes: Vec<Universe>,
     universe: Universe,
     i: usize,
     pub poll_t: Duration,
+    /// `poll_t` to go back to when resuming
+    prev_poll_t: Duration,
     pub area: Area,
+    /// the command being typed, if any
+    command: Option<String>,
+    /// size to keep instead of fitting the terminal, from a `Config`
+    fixed_area: Option<Area>,
+    /// result of the last command, shown in the footer
+    message: Option<String>,
+    /// generations the `message` has been shown for
+    message_gens: u8,
+    pub watermark: Watermark,
+    /// slow down for a few generations if a lot changes at once
+    pub auto_slow: bool,
+    /// births + deaths in one generation to count as an event
+    pub event_threshold: usize,
+    /// generations left to go slower for
+    slow_for: u8,
+    /// where `dump` appends the board to
+    pub dump_path: PathBuf,
+    /// where `save` writes the board to and `load` reads it from
+    pub save_path: PathBuf,
+    /// reseed randomly when the board dies out, stops changing or repeats itself
+    pub auto_restart: bool,
+    /// generations left until reseeding
+    restart_in: Option<u8>,
+    /// seed of the current random board
+    seed: Option<u64>,
+    /// seed to use for the next random board instead of a random one
+    next_seed: Option<u64>,
+    /// draw cells as text with these instead of braille dots
+    pub glyphs: Option<Glyphs>,
+    /// path of the followed component
+    pub trace: Option<Trace>,
+    /// the last few configuration changes, newest last
+    log: VecDeque<String>,
+    /// show `log` over the universe
+    pub show_log: bool,
+    /// use a square universe instead of filling the terminal
+    pub square: bool,
+    /// no colors, `Glyphs::Mono` and an ASCII border
+    pub mono: bool,
+    /// don't tick while typing a command or showing the log
+    pub pause_on_menu: bool,
+    /// called with the universe after every `tick`
+    pub tick_hook: Option<TickHook>,
+    /// generation to pause at, see `App::advance`
+    pub breakpoint: Option<u64>,
+    /// number every 10th row and column around the board
+    pub show_rulers: bool,
+    /// flash the border when too many cells change at once, or everything dies
+    pub flash_on_event: bool,
+    /// generations left to flash the border for
+    pub flash_for: u8,
+    /// only show the live cells and some room around them, zoomed in
+    pub auto_crop_view: bool,
+    /// region shown in the last frame with `auto_crop_view`
+    crop_view: Option<Rect>,
+    /// where the universe was drawn in the last frame
+    board: Option<ui::BoardMap>,
+    /// the last few states before the current one, oldest first: generations and edits
+    history: VecDeque<Universe>,
+    /// states left by `undo`, the next one to `redo` last
+    redo: Vec<Universe>,
+    /// the last cell toggled while dragging the mouse
+    dragged: Option<(u16, u16)>,
+    /// hashes of the last `CYCLE_WINDOW` generations with their number, oldest first
+    recent_hashes: VecDeque<(u64, u64)>,
+    /// period of the cycle the universe is in, if it was found in `recent_hashes`.
+    /// Approximate: different states may have the same hash.
+    pub cycle: Option<u64>,
 }
 impl Default for App {
     fn default() -> Self {
@@ -36,7 +169,42 @@ impl Default for App {
             universe: Universe::default(),
             i: 0,
             poll_t: DEF_DUR,
+            prev_poll_t: DEF_DUR,
             available_universes: shapes::all(),
+            command: None,
+            fixed_area: None,
+            message: None,
+            message_gens: 0,
+            watermark: Watermark::Off,
+            auto_slow: false,
+            event_threshold: DEF_EVENT_THRESHOLD,
+            slow_for: 0,
+            dump_path: std::env::temp_dir().join("cgol-tui.log"),
+            save_path: std::env::temp_dir().join("cgol-tui.cells"),
+            auto_restart: false,
+            restart_in: None,
+            seed: None,
+            next_seed: None,
+            glyphs: None,
+            trace: None,
+            log: VecDeque::with_capacity(LOG_LEN),
+            show_log: false,
+            square: false,
+            mono: false,
+            pause_on_menu: true,
+            tick_hook: None,
+            breakpoint: None,
+            show_rulers: false,
+            flash_on_event: false,
+            flash_for: 0,
+            auto_crop_view: false,
+            crop_view: None,
+            board: None,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            redo: Vec::new(),
+            dragged: None,
+            recent_hashes: VecDeque::with_capacity(CYCLE_WINDOW),
+            cycle: None,
         }
     }
 }
@@ -47,18 +215,99 @@ impl App {
             ..self
         }
     }
+    /// Use `rule` for every pattern
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        for univ in &mut self.available_universes {
+            univ.set_rule(rule.clone());
+        }
+        self.universe.set_rule(rule);
+        self
+    }
+    /// Start with the setup from `config`, the pattern coming first.
+    /// A size given in it is kept for the whole session.
+    pub fn with_config(mut self, config: Config) -> Self {
+        if let Some(univ) = config.universe() {
+            self.fixed_area = config.area.map(|_| univ.area);
+            self.available_universes.insert(0, univ);
+            self.i = 0;
+        }
+        if let Some(rule) = config.rule {
+            self.universe.set_rule(rule);
+        }
+        if let Some(boundary) = config.boundary {
+            self.universe.set_boundary(boundary);
+        }
+        if config.glyphs.is_some() {
+            self.glyphs = config.glyphs;
+        }
+        self
+    }
+    /// Start with the random board from `seed`
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            i: self.available_universes.len() + shapes::RAND,
+            next_seed: Some(seed),
+            ..self
+        }
+    }
     pub fn new(area: Area, available_universes: Vec<Universe>, poll_t: Duration) -> Self {
         App {
             area,
             universe: available_universes[0].clone(),
             i: 0,
             poll_t,
+            prev_poll_t: poll_t,
             available_universes,
+            command: None,
+            fixed_area: None,
+            message: None,
+            message_gens: 0,
+            watermark: Watermark::Off,
+            auto_slow: false,
+            event_threshold: DEF_EVENT_THRESHOLD,
+            slow_for: 0,
+            dump_path: std::env::temp_dir().join("cgol-tui.log"),
+            save_path: std::env::temp_dir().join("cgol-tui.cells"),
+            auto_restart: false,
+            restart_in: None,
+            seed: None,
+            next_seed: None,
+            glyphs: None,
+            trace: None,
+            log: VecDeque::with_capacity(LOG_LEN),
+            show_log: false,
+            square: false,
+            mono: false,
+            pause_on_menu: true,
+            tick_hook: None,
+            breakpoint: None,
+            show_rulers: false,
+            flash_on_event: false,
+            flash_for: 0,
+            auto_crop_view: false,
+            crop_view: None,
+            board: None,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            redo: Vec::new(),
+            dragged: None,
+            recent_hashes: VecDeque::with_capacity(CYCLE_WINDOW),
+            cycle: None,
         }
     }
     pub fn paused(&self) -> bool {
         self.poll_t == PAUSE
     }
+    /// `poll_t`, or slower if an event happened recently
+    pub fn effective_poll_t(&self) -> Duration {
+        if self.slowed_down() {
+            self.poll_t.saturating_mul(SLOW_FACTOR)
+        } else {
+            self.poll_t
+        }
+    }
+    pub fn slowed_down(&self) -> bool {
+        self.slow_for > 0 && !self.paused()
+    }
     pub fn len(&self) -> usize {
         self.available_universes.len() + shapes::N
     }
@@ -79,22 +328,346 @@ impl App {
     //     println!("{}", self.universe);
     // }
 
-    pub fn play_pause(&mut self, prev_poll_t: &mut Duration) {
+    pub fn play_pause(&mut self) {
         if self.paused() {
-            self.poll_t = *prev_poll_t;
+            self.poll_t = self.prev_poll_t;
         } else {
-            *prev_poll_t = self.poll_t;
+            self.prev_poll_t = self.poll_t;
             self.poll_t = PAUSE;
         }
     }
+    /// seed of the current board, if it's random
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+    /// whether the current pattern is `shapes::rand`
+    fn is_random(&self) -> bool {
+        self.i == self.available_universes.len() + shapes::RAND
+    }
+    /// Start the current pattern over, keeping the rule and the boundary mode
     pub fn restart(&mut self) {
+        self.trace = None;
+        self.forget_history();
+        if self.is_random() {
+            let seed = self.next_seed.take().unwrap_or_else(|| fastrand::u64(..));
+            self.reseed(seed);
+            return;
+        }
+        self.seed = None;
         let figur = self.get();
-        self.universe = Universe::from_figur(self.area, figur)
-            .expect("display area should be big enough to fit this figure");
+        let rule = self.universe.rule().clone();
+        let boundary = self.universe.boundary();
+        // a pattern bigger than the canvas is shown clipped
+        let area = self.area.union(figur.area);
+        self.universe = Universe::from_figur(area, figur).expect("area is made to fit the figure");
+        self.universe.set_rule(rule);
+        self.universe.set_boundary(boundary);
+    }
+    /// Record a configuration change, forgetting the oldest one if there are too many
+    fn log(&mut self, entry: impl ToString) {
+        if self.log.len() == LOG_LEN {
+            self.log.pop_front();
+        }
+        self.log.push_back(entry.to_string());
+    }
+    pub fn log_entries(&self) -> impl Iterator<Item = &String> {
+        self.log.iter()
+    }
+    /// Change the size of the universe, keeping the pattern if possible
+    pub fn resize(&mut self, area: Area) {
+        self.area = area;
+        self.log(format!("size → {}x{}", area.width, area.height));
+        if self.universe.area.is_empty() {
+            self.restart();
+        } else {
+            self.trace = None;
+            self.forget_history();
+            // a resized random board isn't the one from its seed anymore
+            self.seed = None;
+            self.universe.resize(area.union(self.get().area));
+        }
+    }
+    /// Cycle through braille dots and the `Glyphs` presets
+    pub fn next_glyphs(&mut self) {
+        self.glyphs = match self.glyphs {
+            None => Some(Glyphs::default()),
+            Some(g) if g.next() == Glyphs::default() => None,
+            Some(g) => Some(g.next()),
+        };
+    }
+    /// Switch between a toroidal and a bounded universe
+    pub fn toggle_boundary(&mut self) {
+        let mode = match self.universe.boundary() {
+            BoundaryMode::Toroidal => BoundaryMode::Dead,
+            BoundaryMode::Dead => BoundaryMode::Toroidal,
+        };
+        self.universe.set_boundary(mode);
+        let name = match mode {
+            BoundaryMode::Toroidal => "toroidal",
+            BoundaryMode::Dead => "bounded",
+        };
+        self.log(format!("topology → {name}"));
     }
 
     pub fn tick(&mut self) {
-        self.universe.tick();
+        self.remember();
+        self.message_gens = self.message_gens.saturating_add(1);
+        if self.message_gens > MESSAGE_GENS {
+            self.message = None;
+        }
+        let prev = (self.auto_slow || self.flash_on_event).then(|| self.universe.clone());
+        if self.recent_hashes.is_empty() {
+            // the starting state may be part of a cycle too
+            let start = (self.universe.state_hash(), self.universe.generation());
+            self.recent_hashes.push_back(start);
+        }
+        let outcome = self.universe.tick();
+        if let Some(prev) = prev {
+            let changed = prev.diff_count(&self.universe);
+            if self.auto_slow {
+                self.on_change(changed);
+            }
+            if self.flash_on_event {
+                let died_out = outcome == TickOutcome::Extinct && prev.population() > 0;
+                self.on_flash_event(changed > self.event_threshold || died_out);
+            }
+        }
+        self.detect_cycle();
+        if self.auto_restart {
+            self.on_outcome(outcome);
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            if !trace.follow(&self.universe) {
+                self.trace = None;
+                self.show("lost the traced component");
+            }
+        }
+        if let Some(hook) = self.tick_hook.as_mut() {
+            hook(&self.universe);
+        }
+    }
+    /// Push the current state onto the `history`, a new one is coming: nothing to `redo` anymore
+    fn remember(&mut self) {
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.universe.clone());
+        self.redo.clear();
+    }
+    /// The universe was replaced, its past is unrelated
+    fn forget_history(&mut self) {
+        self.history.clear();
+        self.redo.clear();
+        self.recent_hashes.clear();
+        self.cycle = None;
+    }
+    /// Look for the current state among the `recent_hashes`, then remember it
+    fn detect_cycle(&mut self) {
+        let hash = self.universe.state_hash();
+        let generation = self.universe.generation();
+        // after going back, later generations may be remembered too
+        self.cycle = self
+            .recent_hashes
+            .iter()
+            .rev()
+            .filter(|&&(h, _)| h == hash)
+            .find_map(|&(_, gen)| generation.checked_sub(gen).filter(|&period| period > 0));
+        if self.recent_hashes.len() == CYCLE_WINDOW {
+            self.recent_hashes.pop_front();
+        }
+        self.recent_hashes.push_back((hash, generation));
+    }
+    /// Go back to the previous generation or edit, `false` if there's none in the `history`
+    pub fn undo(&mut self) -> bool {
+        let Some(prev) = self.history.pop_back() else {
+            return false;
+        };
+        self.redo.push(std::mem::replace(&mut self.universe, prev));
+        true
+    }
+    /// Go forward to the state left by the last `undo`, `false` if there's none
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.history
+            .push_back(std::mem::replace(&mut self.universe, next));
+        true
+    }
+    /// `tick` once while paused, nothing otherwise
+    pub fn step(&mut self) {
+        if self.paused() {
+            self.tick();
+        }
+    }
+    /// Go back to the previous generation, undoing the edits made since.
+    /// If counting started over, by clearing for one, go back to before that.
+    /// Does nothing and returns `false` if it's not in the `history` anymore,
+    /// only the last `HISTORY_LEN` states are kept.
+    pub fn step_back(&mut self) -> bool {
+        let now = self.universe.generation();
+        if self.history.iter().any(|u| u.generation() < now) {
+            while self.universe.generation() == now && self.undo() {}
+            return true;
+        }
+        if self.history.iter().any(|u| u.generation() > now) {
+            while self.universe.generation() <= now && self.undo() {}
+            return true;
+        }
+        false
+    }
+    /// Go back to generation `target`: from the `history`, or by running the random board
+    /// again from its seed. Replaying goes all the way to the current generation,
+    /// and is refused if it doesn't end up exactly at the current board.
+    ///
+    /// # Errors
+    ///
+    /// if `target` isn't in the past, or isn't in the `history` and can't be replayed:
+    /// the board isn't random, or it was changed since it was seeded
+    pub fn step_back_or_replay(&mut self, target: u64) -> Result<(), String> {
+        let now = self.universe.generation();
+        if target >= now {
+            return Err(format!(
+                "generation {target} isn't in the past, this is {now}"
+            ));
+        }
+        if let Some(i) = self.history.iter().position(|u| u.generation() == target) {
+            self.history.truncate(i + 1);
+            self.universe = self.history.pop_back().expect("just found");
+            self.redo.clear();
+            return Ok(());
+        }
+        let replayed = self
+            .seed
+            .and_then(|seed| self.replay(seed, target))
+            .ok_or_else(|| {
+                format!("can't go back to generation {target}: it's not in the history, and the board can't be replayed")
+            })?;
+        self.forget_history();
+        self.universe = replayed;
+        Ok(())
+    }
+    /// The random board from `seed` at generation `target`,
+    /// if it evolves into exactly the current board
+    fn replay(&self, seed: u64, target: u64) -> Option<Universe> {
+        let mut univ = shapes::rand_seeded(self.universe.area, seed);
+        univ.set_rule(self.universe.rule().clone());
+        univ.set_boundary(self.universe.boundary());
+        for region in self.universe.region_rules() {
+            univ.add_region_rule(region.region, region.rule.clone());
+        }
+        let mut at_target = None;
+        while univ.generation() < self.universe.generation() {
+            if univ.generation() == target {
+                at_target = Some(univ.clone());
+            }
+            univ.tick();
+        }
+        at_target.filter(|_| univ.cells == self.universe.cells)
+    }
+    /// whether the command palette or the log is open
+    pub fn menu_open(&self) -> bool {
+        self.command.is_some() || self.show_log
+    }
+    /// `advance`, unless a menu is open and `pause_on_menu` is set
+    pub fn on_timeout(&mut self) {
+        if !(self.pause_on_menu && self.menu_open()) {
+            self.advance();
+        }
+    }
+    /// Show `msg` in the footer for `MESSAGE_GENS` generations, or until a key is pressed
+    fn show(&mut self, msg: impl Into<String>) {
+        self.message = Some(msg.into());
+        self.message_gens = 0;
+    }
+    /// `tick`, then pause if `breakpoint` is reached
+    pub fn advance(&mut self) {
+        self.tick();
+        if !self.paused() && self.breakpoint == Some(self.universe.generation()) {
+            self.play_pause();
+            self.show(format!("reached generation {}", self.universe.generation()));
+        }
+    }
+    /// `tick` until more than `threshold` cells change in one generation, the board stops changing,
+    /// the `breakpoint` is reached or `SKIP_MAX` generations passed, like `Universe::run_until_delta`
+    fn skip(&mut self, threshold: usize) {
+        let mut skipped = 0;
+        while skipped < SKIP_MAX {
+            self.tick();
+            skipped += 1;
+            if self.breakpoint == Some(self.universe.generation()) {
+                // stay there, like `advance`
+                if !self.paused() {
+                    self.play_pause();
+                }
+                self.show(format!(
+                    "skipped {skipped} generations, reached generation {}",
+                    self.universe.generation()
+                ));
+                return;
+            }
+            // the state before the tick, none if the board was replaced
+            let Some(prev) = self.history.back() else {
+                break;
+            };
+            let changed = prev.diff_count(&self.universe);
+            if changed > threshold || changed == 0 || self.universe.population() == 0 {
+                break;
+            }
+        }
+        self.show(format!("skipped {skipped} generations"));
+    }
+    /// Start following the component closest to the middle, or stop following
+    pub fn toggle_trace(&mut self) {
+        self.trace = if self.trace.is_some() {
+            None
+        } else {
+            let (row, col) = (
+                f64::from(self.universe.height()) / 2.,
+                f64::from(self.universe.width()) / 2.,
+            );
+            Trace::select(&self.universe, row, col)
+        };
+    }
+    /// Count down to reseeding if the board is dead, stable or cycling, like ash of blinkers does
+    fn on_outcome(&mut self, outcome: TickOutcome) {
+        if outcome == TickOutcome::Changed && self.cycle.is_none() {
+            self.restart_in = None;
+            return;
+        }
+        match self.restart_in {
+            None => self.restart_in = Some(RESTART_DELAY),
+            Some(0) => {
+                self.restart_in = None;
+                self.reseed(fastrand::u64(..));
+            }
+            Some(n) => self.restart_in = Some(n - 1),
+        }
+    }
+    /// Start over with a random board from `seed`, keeping the rule and the boundary mode
+    pub fn reseed(&mut self, seed: u64) {
+        let mut univ = shapes::rand_seeded(self.area, seed);
+        univ.set_rule(self.universe.rule().clone());
+        univ.set_boundary(self.universe.boundary());
+        self.universe = univ;
+        self.seed = Some(seed);
+        self.forget_history();
+    }
+    /// Slow down if `changed` cells in the last generation is an event
+    fn on_change(&mut self, changed: usize) {
+        if changed > self.event_threshold {
+            self.slow_for = SLOW_GENS;
+        } else {
+            self.slow_for = self.slow_for.saturating_sub(1);
+        }
+    }
+    /// Start flashing on an `event`, or count down
+    fn on_flash_event(&mut self, event: bool) {
+        if event {
+            self.flash_for = FLASH_GENS;
+        } else {
+            self.flash_for = self.flash_for.saturating_sub(1);
+        }
     }
 
     pub fn faster(&mut self, big: bool) {
@@ -103,7 +676,8 @@ impl App {
             self.poll_t = self
                 .poll_t
                 .checked_sub(self.poll_t.checked_div(div).unwrap_or(DEF_DUR))
-                .unwrap_or(DEF_DUR);
+                .unwrap_or(DEF_DUR)
+                .clamp(MIN_POLL, MAX_POLL);
         }
     }
     pub fn slower(&mut self, big: bool) {
@@ -112,7 +686,157 @@ impl App {
             self.poll_t = self
                 .poll_t
                 .checked_add(self.poll_t.checked_div(div).unwrap_or(DEF_DUR))
-                .unwrap_or(DEF_DUR);
+                .unwrap_or(DEF_DUR)
+                .clamp(MIN_POLL, MAX_POLL);
+        }
+    }
+
+    /// Tick `fps` times a second, as far as `MIN_POLL` and `MAX_POLL` allow, unpausing
+    ///
+    /// # Errors
+    ///
+    /// if `fps` isn't positive
+    pub fn set_fps(&mut self, fps: f64) -> Result<(), String> {
+        if fps.is_nan() || fps <= 0.0 {
+            return Err(format!("invalid fps {fps}, should be positive"));
+        }
+        self.poll_t = Duration::from_secs_f64(1.0 / fps).clamp(MIN_POLL, MAX_POLL);
+        Ok(())
+    }
+
+    /// Append the current board in the `.cells` format to `dump_path`
+    ///
+    /// # Errors
+    ///
+    /// if writing to `dump_path` fails
+    pub fn dump(&self) -> io::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.dump_path)?;
+        writeln!(
+            file,
+            "{:#}!Generation: {}\n",
+            self.universe,
+            self.universe.generation()
+        )
+    }
+
+    /// Write the current board to `save_path`
+    ///
+    /// # Errors
+    ///
+    /// if writing to `save_path` fails
+    pub fn save(&self) -> io::Result<()> {
+        self.universe.save_to_path(&self.save_path)
+    }
+
+    /// Replace the current board with the one at `save_path`, keeping the rule and the topology
+    ///
+    /// # Errors
+    ///
+    /// if it can't be read or is invalid, the current board is kept then
+    pub fn load(&mut self) -> Result<(), String> {
+        let loaded = Universe::load_from_path(&self.save_path)?;
+        let rule = self.universe.rule().clone();
+        let boundary = self.universe.boundary();
+        let area = self.area.union(loaded.area);
+        self.universe = Universe::from_figur(area, loaded).expect("area is made to fit the figure");
+        self.universe.set_rule(rule);
+        self.universe.set_boundary(boundary);
+        self.seed = None;
+        self.forget_history();
+        Ok(())
+    }
+
+    /// Parse and run `cmd`
+    ///
+    /// # Errors
+    ///
+    /// if `cmd` is invalid, or refers to cells out of range
+    pub fn execute(&mut self, cmd: &str) -> Result<(), String> {
+        match cmd.parse()? {
+            Command::Toggle(coords) => {
+                self.universe.check_in_range(&coords)?;
+                self.remember();
+                self.universe.toggle_cells(&coords)?;
+            }
+            Command::Fill { from, to, cell } => {
+                self.universe.check_in_range(&[from, to])?;
+                self.remember();
+                for row in from.0.min(to.0)..=from.0.max(to.0) {
+                    for col in from.1.min(to.1)..=from.1.max(to.1) {
+                        self.universe.set_cell(row, col, cell);
+                    }
+                }
+            }
+            Command::Rule(rule) => {
+                self.universe.apply_rule_string(&rule)?;
+                self.log(format!("rule → {}", self.universe.rule()));
+            }
+            Command::Break(generation) => self.breakpoint = generation,
+            Command::Fps(fps) => self.set_fps(f64::from(fps))?,
+            Command::Load { path, rule } => {
+                let mut univ = Universe::load(path.as_ref())?;
+                if let Some(rule) = rule {
+                    univ.apply_rule_string(&rule)?;
+                }
+                self.universe.set_rule(univ.rule().clone());
+                self.available_universes.insert(0, univ);
+                self.i = 0;
+                self.restart();
+                self.log(format!("rule → {}", self.universe.rule()));
+            }
+            Command::Skip(threshold) => self.skip(threshold),
+            #[cfg(feature = "image")]
+            Command::Png { path, cell_px } => {
+                self.universe
+                    .to_png(path.as_ref(), cell_px)
+                    .map_err(|e| format!("couldn't write {path}: {e}"))?;
+                self.show(format!("wrote {path}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a key press while typing a command
+    fn command_key(&mut self, code: KeyCode) {
+        let Some(cmd) = self.command.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.command = None,
+            // deleting from an empty command closes it
+            KeyCode::Backspace if cmd.pop().is_none() => self.command = None,
+            KeyCode::Char(ch) => cmd.push(ch),
+            KeyCode::Enter => {
+                let cmd = self.command.take().unwrap_or_default();
+                if let Err(e) = self.execute(&cmd) {
+                    self.show(e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Toggle the cell clicked on with the left button, and the ones dragged over
+    pub fn mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.dragged = None,
+            MouseEventKind::Drag(MouseButton::Left) => {}
+            _ => return,
+        }
+        let Some(cell) = self
+            .board
+            .and_then(|board| board.cell_at(mouse.column, mouse.row))
+        else {
+            return;
+        };
+        // the universe might have changed since it was drawn
+        if self.dragged != Some(cell) && self.universe.check_in_range(&[cell]).is_ok() {
+            self.remember();
+            self.universe.toggle_cell(cell.0, cell.1);
+            self.dragged = Some(cell);
         }
     }
 
@@ -133,35 +857,120 @@ impl App {
         self.restart();
     }
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
-        let mut prev_poll_t = self.poll_t;
-
         loop {
             terminal.draw(|f| ui::ui(f, self))?;
 
             // Wait up to `poll_t` for another event
-            if event::poll(self.poll_t)? {
-                if let Event::Key(key) = event::read()? {
+            if event::poll(self.effective_poll_t())? {
+                let event = event::read()?;
+                if let Event::Mouse(mouse) = event {
+                    self.mouse(mouse);
+                }
+                if let Event::Key(key) = event {
                     if key.kind != KeyEventKind::Press {
                         continue;
                     }
+                    if self.command.is_some() {
+                        self.command_key(key.code);
+                        continue;
+                    }
+                    // the footer shows the stats again
+                    self.message = None;
+                    self.message_gens = 0;
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => break,
                         KeyCode::Char('j') | KeyCode::Down => self.slower(false),
                         KeyCode::Char('k') | KeyCode::Up => self.faster(false),
-                        KeyCode::Char(' ') | KeyCode::Enter => self.play_pause(&mut prev_poll_t),
+                        KeyCode::Char(' ') | KeyCode::Enter => self.play_pause(),
+                        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            self.message = (!self.redo()).then(|| "nothing to redo".into());
+                        }
                         KeyCode::Char('r') => self.restart(),
                         KeyCode::Char('n' | 'l') | KeyCode::Right => self.next(),
                         KeyCode::Char('p' | 'h') | KeyCode::Left => self.prev(),
                         KeyCode::Char('R') | KeyCode::Backspace => *self = Self::default(),
+                        KeyCode::Char('w') => self.watermark = self.watermark.next(),
+                        KeyCode::Char('b') => self.toggle_boundary(),
+                        KeyCode::Char('g') => self.next_glyphs(),
+                        KeyCode::Char('t') => self.toggle_trace(),
+                        KeyCode::Char('L') => self.show_log = !self.show_log,
+                        KeyCode::Char('f') => self.square = !self.square,
+                        KeyCode::Char('e') => {
+                            self.auto_restart = !self.auto_restart;
+                            self.restart_in = None;
+                        }
+                        KeyCode::Char('c') => {
+                            if let Some(seed) = self.seed {
+                                crate::term::copy_to_clipboard(
+                                    &mut io::stdout(),
+                                    &seed.to_string(),
+                                )?;
+                                self.show(format!("copied seed: {seed}"));
+                            }
+                        }
+                        KeyCode::Char('d') => {
+                            let msg = match self.dump() {
+                                Ok(()) => format!("dumped to {}", self.dump_path.display()),
+                                Err(e) => format!("couldn't dump: {e}"),
+                            };
+                            self.show(msg);
+                        }
+                        KeyCode::Char('s') => {
+                            let msg = match self.save() {
+                                Ok(()) => format!("saved to {}", self.save_path.display()),
+                                Err(e) => format!("couldn't save: {e}"),
+                            };
+                            self.show(msg);
+                        }
+                        KeyCode::Char('o') => {
+                            let msg = match self.load() {
+                                Ok(()) => format!("loaded {}", self.save_path.display()),
+                                Err(e) => format!("couldn't load: {e}"),
+                            };
+                            self.show(msg);
+                        }
+                        KeyCode::Char('a') => {
+                            self.auto_slow = !self.auto_slow;
+                            self.slow_for = 0;
+                        }
+                        KeyCode::Char('u') => self.show_rulers = !self.show_rulers,
+                        KeyCode::Char('x') => {
+                            self.remember();
+                            self.universe.clear();
+                            // a blank canvas, not a random board anymore
+                            self.seed = None;
+                        }
+                        KeyCode::Char('i') => {
+                            self.remember();
+                            self.universe.invert();
+                            self.seed = None;
+                        }
+                        KeyCode::Char('.') => self.step(),
+                        KeyCode::Char(',') => {
+                            self.message =
+                                (!self.step_back()).then(|| "no earlier generation".into());
+                        }
+                        KeyCode::Char('U') => {
+                            self.message = (!self.undo()).then(|| "nothing to undo".into());
+                        }
+                        KeyCode::Char('z') => {
+                            self.auto_crop_view = !self.auto_crop_view;
+                            self.crop_view = None;
+                        }
+                        KeyCode::Char('v') => {
+                            self.flash_on_event = !self.flash_on_event;
+                            self.flash_for = 0;
+                        }
+                        KeyCode::Char(':') => {
+                            self.command = Some(String::new());
+                        }
                         _ => {}
                     }
-                } else {
-                    // resize and restart
-                    self.restart();
                 }
+                // resizing is handled when drawing
             } else {
                 // Timeout expired, updating life state
-                self.tick();
+                self.on_timeout();
             }
         }
 
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Area {
     pub width: u16,
     pub height: u16,
@@ -10,6 +10,14 @@ impl Area {
     pub const fn len(&self) -> usize {
         self.width as usize * self.height as usize
     }
+    /// Big enough to hold both `self` and `other`
+    pub fn union(self, other: Area) -> Area {
+        Area::new(self.width.max(other.width), self.height.max(other.height))
+    }
+    /// Whether `other` fits into `self`
+    pub fn contains(self, other: Area) -> bool {
+        other.width <= self.width && other.height <= self.height
+    }
     #[must_use]
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
@@ -1,10 +1,16 @@
 /// information about one `Cell`: either `Dead` or `Alive`
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum Cell {
     #[default]
     Dead = 0,
     Alive = 1,
 }
+impl Cell {
+    /// `Dead` ↔ `Alive`
+    pub fn toggle(&mut self) {
+        *self = !*self;
+    }
+}
 impl From<bool> for Cell {
     fn from(alive: bool) -> Self {
         if alive {
@@ -14,6 +20,47 @@ impl From<bool> for Cell {
         }
     }
 }
+impl From<Cell> for bool {
+    fn from(cell: Cell) -> Self {
+        cell == Cell::Alive
+    }
+}
+impl std::ops::Not for Cell {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        (!bool::from(self)).into()
+    }
+}
+impl std::ops::BitOr for Cell {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        (bool::from(self) | bool::from(rhs)).into()
+    }
+}
+impl std::ops::BitAnd for Cell {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        (bool::from(self) & bool::from(rhs)).into()
+    }
+}
+impl std::ops::BitXor for Cell {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        (bool::from(self) ^ bool::from(rhs)).into()
+    }
+}
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Alive => 'O',
+            Cell::Dead => '.',
+        }
+    }
+}
 impl TryFrom<char> for Cell {
     type Error = String;
 
@@ -0,0 +1,132 @@
+use crate::app::Cell;
+use std::str::FromStr;
+
+/// Commands that can be typed after pressing `:`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `toggle <row>,<col> ...`: toggle the given cells
+    Toggle(Vec<(u16, u16)>),
+    /// `rule <B/S rule>`: change the rule
+    Rule(String),
+    /// `fill <row>,<col> <row>,<col> [alive|dead]`: set every cell in the rectangle
+    /// between the two corners, alive by default
+    Fill {
+        from: (u16, u16),
+        to: (u16, u16),
+        cell: Cell,
+    },
+    /// `skip <N>`: tick until more than `N` cells change in one generation
+    Skip(usize),
+    /// `break [N]`: pause when reaching generation `N`, or don't pause anymore
+    Break(Option<u64>),
+    /// `load <path> [as <B/S rule>]`: load a pattern, with `rule` instead of its own
+    Load { path: String, rule: Option<String> },
+    /// `fps <N>`: tick `N` times a second
+    Fps(u16),
+    /// `png <path> [cell px]`: draw the board into a PNG, 4 pixels a cell by default
+    #[cfg(feature = "image")]
+    Png { path: String, cell_px: u32 },
+}
+
+/// Parse a `<row>,<col>` pair
+pub fn parse_coord(s: &str) -> Result<(u16, u16), String> {
+    let (row, col) = s
+        .split_once(',')
+        .ok_or_else(|| format!("invalid coordinate {s:?}, should be <row>,<col>"))?;
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<u16>()
+            .map_err(|e| format!("invalid coordinate {s:?}: {e}"))
+    };
+    Ok((parse(row)?, parse(col)?))
+}
+
+/// Parse whitespace separated `<row>,<col>` pairs
+pub fn parse_coords(s: &str) -> Result<Vec<(u16, u16)>, String> {
+    s.split_whitespace().map(parse_coord).collect()
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        match words.next() {
+            Some("toggle" | "t") => {
+                let coords = words.map(parse_coord).collect::<Result<Vec<_>, _>>()?;
+                if coords.is_empty() {
+                    return Err("usage: toggle <row>,<col> ...".into());
+                }
+                Ok(Command::Toggle(coords))
+            }
+            Some("rule") => match words.next() {
+                Some(rule) => Ok(Command::Rule(rule.into())),
+                None => Err("usage: rule <B/S rule>, eg.: rule B36/S23".into()),
+            },
+            Some("fill") => {
+                let usage = "usage: fill <row>,<col> <row>,<col> [alive|dead]";
+                let (Some(from), Some(to)) = (words.next(), words.next()) else {
+                    return Err(usage.into());
+                };
+                let cell = match words.next() {
+                    None | Some("alive") => Cell::Alive,
+                    Some("dead") => Cell::Dead,
+                    Some(_) => return Err(usage.into()),
+                };
+                Ok(Command::Fill {
+                    from: parse_coord(from)?,
+                    to: parse_coord(to)?,
+                    cell,
+                })
+            }
+            Some("skip") => {
+                let usage = "usage: skip <N>, eg.: skip 20";
+                let n = words.next().ok_or(usage)?;
+                n.parse()
+                    .map(Command::Skip)
+                    .map_err(|e| format!("invalid threshold {n:?}: {e}"))
+            }
+            Some("break") => match words.next() {
+                Some(n) => n
+                    .parse()
+                    .map(|n| Command::Break(Some(n)))
+                    .map_err(|e| format!("invalid generation {n:?}: {e}")),
+                None => Ok(Command::Break(None)),
+            },
+            Some("fps") => {
+                let usage = "usage: fps <N>, eg.: fps 30";
+                let n = words.next().ok_or(usage)?;
+                n.parse()
+                    .map(Command::Fps)
+                    .map_err(|e| format!("invalid fps {n:?}: {e}"))
+            }
+            Some("load") => {
+                let usage = "usage: load <path> [as <B/S rule>]";
+                let path = words.next().ok_or(usage)?.into();
+                let rule = match (words.next(), words.next(), words.next()) {
+                    (None, _, _) => None,
+                    (Some("as"), Some(rule), None) => Some(rule.into()),
+                    _ => return Err(usage.into()),
+                };
+                Ok(Command::Load { path, rule })
+            }
+            #[cfg(feature = "image")]
+            Some("png") => {
+                let usage = "usage: png <path> [cell px], eg.: png board.png 8";
+                let path = words.next().ok_or(usage)?.into();
+                let cell_px = match (words.next(), words.next()) {
+                    (None, _) => 4,
+                    (Some(n), None) => n
+                        .parse()
+                        .map_err(|e| format!("invalid cell size {n:?}: {e}"))?,
+                    (Some(_), Some(_)) => return Err(usage.into()),
+                };
+                Ok(Command::Png { path, cell_px })
+            }
+            #[cfg(not(feature = "image"))]
+            Some("png") => Err("png needs the image feature".into()),
+            Some(cmd) => Err(format!("unknown command: {cmd:?}")),
+            None => Err("empty command".into()),
+        }
+    }
+}
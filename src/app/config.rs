@@ -0,0 +1,206 @@
+use crate::app::{Area, BoundaryMode, Glyphs, Rule, Universe};
+#[cfg(feature = "config")]
+use std::{path::Path, str::FromStr};
+
+/// A whole experiment setup, in a small subset of TOML:
+///
+/// ```toml
+/// # comment
+/// width = 80
+/// height = 40
+/// rule = "B36/S23"
+/// topology = "bounded" # or "toroidal"
+/// glyphs = "emoji"
+/// pattern = "x = 3, y = 3\nbo$2bo$3o!" # RLE or .cells, or
+/// pattern_file = "glider.rle" # relative to the config file
+/// ```
+///
+/// Everything is optional. Reading and writing it needs the `config` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Config {
+    /// size of the board the pattern is placed in, grown to fit it,
+    /// then kept instead of fitting the terminal
+    pub area: Option<Area>,
+    pub rule: Option<Rule>,
+    pub boundary: Option<BoundaryMode>,
+    pub glyphs: Option<Glyphs>,
+    pub pattern: Option<Universe>,
+}
+
+#[cfg(feature = "config")]
+impl Config {
+    /// Read and parse the config at `path`
+    ///
+    /// # Errors
+    ///
+    /// if it can't be read, or is invalid
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read config {path:?}: {e}"))?;
+        Self::parse(&s, path.parent().unwrap_or(Path::new("")))
+            .map_err(|e| format!("invalid config {path:?}: {e}"))
+    }
+
+    /// Parse `s`, reading `pattern_file` relative to `dir`
+    fn parse(s: &str, dir: &Path) -> Result<Self, String> {
+        let mut config = Config::default();
+        let (mut width, mut height) = (None, None);
+        for (i, line) in s.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |why: String| format!("line {}: {why}", i + 1);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected <key> = <value>".into()))?;
+            let value = value.trim();
+            let string = || parse_string(value).map_err(err);
+            let number = || {
+                value
+                    .parse::<u16>()
+                    .map_err(|e| err(format!("invalid number {value:?}: {e}")))
+            };
+            match key.trim() {
+                "width" => width = Some(number()?),
+                "height" => height = Some(number()?),
+                "rule" => config.rule = Some(Rule::parse(&string()?).map_err(err)?),
+                "topology" => {
+                    config.boundary = Some(match string()?.as_str() {
+                        "toroidal" => BoundaryMode::Toroidal,
+                        "bounded" => BoundaryMode::Dead,
+                        other => {
+                            return Err(err(format!(
+                                "invalid topology {other:?}, should be toroidal or bounded"
+                            )))
+                        }
+                    });
+                }
+                "glyphs" => config.glyphs = Some(string()?.parse().map_err(err)?),
+                "pattern" => config.pattern = Some(Universe::parse(&string()?).map_err(err)?),
+                "pattern_file" => {
+                    let path = dir.join(string()?);
+                    config.pattern = Some(Universe::load(&path).map_err(err)?);
+                }
+                other => return Err(err(format!("unknown key {other:?}"))),
+            }
+        }
+        config.area = match (width, height) {
+            (Some(width), Some(height)) => Some(Area::new(width, height)),
+            (None, None) => None,
+            _ => return Err("width and height should be given together".into()),
+        };
+        Ok(config)
+    }
+}
+
+impl Config {
+    /// The board to start with: the pattern in the middle of `area`, if any of them are given
+    pub fn universe(&self) -> Option<Universe> {
+        let univ = match (&self.pattern, self.area) {
+            (Some(pattern), area) => {
+                let area = area.unwrap_or_default().union(pattern.area);
+                Universe::from_figur(area, pattern.clone()).expect("area is made to fit the figure")
+            }
+            (None, Some(area)) => super::shapes::empty(area),
+            (None, None) => return None,
+        };
+        Some(univ)
+    }
+}
+
+#[cfg(feature = "config")]
+impl FromStr for Config {
+    type Err = String;
+
+    /// `pattern_file` is relative to the working directory
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Path::new(""))
+    }
+}
+
+/// Writes the pattern embedded as RLE
+#[cfg(feature = "config")]
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(area) = self.area {
+            writeln!(f, "width = {}", area.width)?;
+            writeln!(f, "height = {}", area.height)?;
+        }
+        if let Some(rule) = &self.rule {
+            writeln!(f, "rule = {}", quote(&rule.to_string()))?;
+        }
+        if let Some(boundary) = self.boundary {
+            let topology = match boundary {
+                BoundaryMode::Toroidal => "toroidal",
+                BoundaryMode::Dead => "bounded",
+            };
+            writeln!(f, "topology = {}", quote(topology))?;
+        }
+        if let Some(glyphs) = self.glyphs {
+            writeln!(f, "glyphs = {}", quote(glyphs.name()))?;
+        }
+        if let Some(pattern) = &self.pattern {
+            writeln!(f, "pattern = {}", quote(&pattern.to_rle()))?;
+        }
+        Ok(())
+    }
+}
+
+/// `line` without a `#` comment outside of a string
+#[cfg(feature = "config")]
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// A `"`-quoted string, with `\n`, `\"` and `\\` escapes
+#[cfg(feature = "config")]
+fn parse_string(value: &str) -> Result<String, String> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a \"quoted\" string, got {value:?}"))?;
+    let mut s = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            s.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => s.push('\n'),
+            Some('"') => s.push('"'),
+            Some('\\') => s.push('\\'),
+            other => return Err(format!("invalid escape \\{}", other.unwrap_or(' '))),
+        }
+    }
+    Ok(s)
+}
+
+/// Inverse of `parse_string`
+#[cfg(feature = "config")]
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '\n' => quoted.push_str("\\n"),
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
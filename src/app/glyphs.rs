@@ -0,0 +1,76 @@
+use crate::app::Cell;
+use ratatui::text::Span;
+use std::str::FromStr;
+
+/// Named pairs of symbols to draw live and dead cells with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Glyphs {
+    /// `◼`/`◻`
+    #[default]
+    Squares,
+    /// `●`/`○`
+    Circles,
+    /// `█`/`░`
+    Blocks,
+    /// `🟩`/`⬛`, double-width
+    Emoji,
+    /// `██`/`  `, for `App::mono`, not in `ALL`
+    Mono,
+}
+
+impl Glyphs {
+    pub const ALL: [Glyphs; 4] = [
+        Glyphs::Squares,
+        Glyphs::Circles,
+        Glyphs::Blocks,
+        Glyphs::Emoji,
+    ];
+    /// (alive, dead)
+    pub fn pair(self) -> (&'static str, &'static str) {
+        match self {
+            Glyphs::Squares => ("◼", "◻"),
+            Glyphs::Circles => ("●", "○"),
+            Glyphs::Blocks => ("█", "░"),
+            Glyphs::Emoji => ("🟩", "⬛"),
+            Glyphs::Mono => ("██", "  "),
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            Glyphs::Squares => "squares",
+            Glyphs::Circles => "circles",
+            Glyphs::Blocks => "blocks",
+            Glyphs::Emoji => "emoji",
+            Glyphs::Mono => "mono",
+        }
+    }
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&g| g == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+    /// `cell` drawn with these glyphs, padded to 2 columns
+    pub fn cell(self, cell: Cell) -> String {
+        let (alive, dead) = self.pair();
+        let symbol = if cell == Cell::Alive { alive } else { dead };
+        // double-width ones, like emoji need no padding
+        if Span::raw(symbol).width() >= 2 {
+            symbol.to_string()
+        } else {
+            format!("{symbol} ")
+        }
+    }
+}
+
+impl FromStr for Glyphs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Glyphs::name).join(", ");
+                format!("unknown glyphs {s:?}, should be one of: {names}")
+            })
+    }
+}
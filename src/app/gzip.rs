@@ -0,0 +1,334 @@
+use super::checksum::crc32;
+
+/// Magic bytes at the start of every gzip file
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Most bits a DEFLATE Huffman code can have
+const MAX_BITS: usize = 15;
+/// Most bytes `decompress` inflates to: a pattern is never this big, a gzip bomb is
+const MAX_SIZE: usize = 64 << 20;
+
+/// Whether `data` looks like gzip
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
+/// Decompress a gzip member of at most `MAX_SIZE` bytes
+///
+/// # Errors
+///
+/// if `data` isn't valid gzip, the checksum or the size doesn't match, or it's too big
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    decompress_at_most(data, MAX_SIZE)
+}
+
+/// `decompress`, with at most `limit` bytes of output
+///
+/// # Errors
+///
+/// same as `decompress`
+pub fn decompress_at_most(data: &[u8], limit: usize) -> Result<Vec<u8>, String> {
+    let err = |why: &str| format!("invalid gzip: {why}");
+    if !is_gzip(data) {
+        return Err(err("missing magic bytes"));
+    }
+    let header = data.get(..10).ok_or_else(|| err("truncated header"))?;
+    if header[2] != 8 {
+        return Err(err("unknown compression method"));
+    }
+    let flags = header[3];
+    let mut pos = 10;
+    // extra field
+    if flags & 4 != 0 {
+        let len = data
+            .get(pos..pos + 2)
+            .ok_or_else(|| err("truncated header"))?;
+        pos += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    // file name, then comment: zero-terminated
+    for flag in [8, 16] {
+        if flags & flag != 0 {
+            let len = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or_else(|| err("truncated header"))?;
+            pos += len + 1;
+        }
+    }
+    // header checksum
+    if flags & 2 != 0 {
+        pos += 2;
+    }
+    let body = data.get(pos..).ok_or_else(|| err("truncated header"))?;
+
+    let (out, read) = inflate(body, limit).map_err(|why| err(&why))?;
+
+    // the trailer is byte aligned
+    let trailer = body
+        .get(read..read + 8)
+        .ok_or_else(|| err("truncated trailer"))?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc != crc32(&out) {
+        return Err(err("checksum mismatch"));
+    }
+    if size != out.len() as u32 {
+        return Err(err("size mismatch"));
+    }
+    Ok(out)
+}
+
+/// Decompress a raw DEFLATE stream of at most `limit` bytes,
+/// also returning the number of bytes it took up
+///
+/// # Errors
+///
+/// if it isn't valid DEFLATE, or it's longer
+pub fn inflate(data: &[u8], limit: usize) -> Result<(Vec<u8>, usize), String> {
+    let mut inflater = Inflater {
+        input: data,
+        pos: 0,
+        bit_buf: 0,
+        bit_count: 0,
+        out: Vec::new(),
+        limit,
+    };
+    inflater.inflate()?;
+    Ok((inflater.out, inflater.pos))
+}
+
+/// Canonical Huffman code: number of codes of each length, symbols ordered by code
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// From the code length of each symbol, 0 meaning unused
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for &len in lengths {
+            counts[usize::from(len)] += 1;
+        }
+        counts[0] = 0;
+        // more codes than lengths allow
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = left * 2 - i32::from(count);
+            if left < 0 {
+                return Err("over-subscribed Huffman code".into());
+            }
+        }
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for len in 1..=MAX_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[usize::from(offsets[usize::from(len)])] = symbol as u16;
+                offsets[usize::from(len)] += 1;
+            }
+        }
+        Ok(Huffman { counts, symbols })
+    }
+}
+
+/// State of decompressing a DEFLATE stream
+struct Inflater<'a> {
+    input: &'a [u8],
+    /// next byte to read from `input`
+    pos: usize,
+    bit_buf: u32,
+    bit_count: u8,
+    out: Vec<u8>,
+    /// most bytes `out` may grow to
+    limit: usize,
+}
+
+impl Inflater<'_> {
+    /// Make sure `n` more bytes of output fit into the `limit`
+    fn room_for(&self, n: usize) -> Result<(), String> {
+        if self.out.len() + n > self.limit {
+            return Err(format!("more than {} bytes of output", self.limit));
+        }
+        Ok(())
+    }
+
+    /// The next `n` bits, least significant first
+    fn bits(&mut self, n: u8) -> Result<u32, String> {
+        while self.bit_count < n {
+            let byte = *self.input.get(self.pos).ok_or("unexpected end of data")?;
+            self.pos += 1;
+            self.bit_buf |= u32::from(byte) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let bits = self.bit_buf & ((1 << n) - 1);
+        self.bit_buf >>= n;
+        self.bit_count -= n;
+        Ok(bits)
+    }
+
+    fn decode(&mut self, code: &Huffman) -> Result<u16, String> {
+        let (mut value, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..=MAX_BITS {
+            value |= self.bits(1)? as i32;
+            let count = i32::from(code.counts[len]);
+            if value - count < first {
+                return Ok(code.symbols[(index + value - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            value <<= 1;
+        }
+        Err("invalid Huffman code".into())
+    }
+
+    fn inflate(&mut self) -> Result<(), String> {
+        loop {
+            let last = self.bits(1)? == 1;
+            match self.bits(2)? {
+                0 => self.stored()?,
+                1 => {
+                    let (lit, dist) = fixed_codes()?;
+                    self.codes(&lit, &dist)?;
+                }
+                2 => {
+                    let (lit, dist) = self.dynamic_codes()?;
+                    self.codes(&lit, &dist)?;
+                }
+                _ => return Err("invalid block type".into()),
+            }
+            if last {
+                return Ok(());
+            }
+        }
+    }
+
+    fn stored(&mut self) -> Result<(), String> {
+        // skip to the byte boundary
+        self.bit_buf = 0;
+        self.bit_count = 0;
+        let header = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .ok_or("unexpected end of data")?;
+        let len = u16::from_le_bytes([header[0], header[1]]);
+        let nlen = u16::from_le_bytes([header[2], header[3]]);
+        if len != !nlen {
+            return Err("stored block length mismatch".into());
+        }
+        self.pos += 4;
+        self.room_for(usize::from(len))?;
+        let data = self
+            .input
+            .get(self.pos..self.pos + usize::from(len))
+            .ok_or("unexpected end of data")?;
+        self.out.extend_from_slice(data);
+        self.pos += usize::from(len);
+        Ok(())
+    }
+
+    fn dynamic_codes(&mut self) -> Result<(Huffman, Huffman), String> {
+        const ORDER: [usize; 19] = [
+            16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+        ];
+        let nlen = self.bits(5)? as usize + 257;
+        let ndist = self.bits(5)? as usize + 1;
+        let ncode = self.bits(4)? as usize + 4;
+        let mut lengths = [0u8; 19];
+        for &i in &ORDER[..ncode] {
+            lengths[i] = self.bits(3)? as u8;
+        }
+        let lencode = Huffman::new(&lengths)?;
+
+        let mut lengths = vec![0u8; nlen + ndist];
+        let mut i = 0;
+        while i < nlen + ndist {
+            let symbol = self.decode(&lencode)?;
+            let (len, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 => {
+                    let prev = *lengths[..i]
+                        .last()
+                        .ok_or("repeat with no previous length")?;
+                    (prev, 3 + self.bits(2)?)
+                }
+                17 => (0, 3 + self.bits(3)?),
+                _ => (0, 11 + self.bits(7)?),
+            };
+            let end = i + repeat as usize;
+            lengths
+                .get_mut(i..end)
+                .ok_or("too many code lengths")?
+                .fill(len);
+            i = end;
+        }
+        if lengths[256] == 0 {
+            return Err("missing end-of-block code".into());
+        }
+        Ok((
+            Huffman::new(&lengths[..nlen])?,
+            Huffman::new(&lengths[nlen..])?,
+        ))
+    }
+
+    /// Decode literals and back-references until the end of the block
+    fn codes(&mut self, lit: &Huffman, dist: &Huffman) -> Result<(), String> {
+        const LEN_BASE: [u16; 29] = [
+            3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99,
+            115, 131, 163, 195, 227, 258,
+        ];
+        const LEN_EXTRA: [u8; 29] = [
+            0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+        ];
+        const DIST_BASE: [u16; 30] = [
+            1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025,
+            1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+        ];
+        const DIST_EXTRA: [u8; 30] = [
+            0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12,
+            12, 13, 13,
+        ];
+        loop {
+            let symbol = self.decode(lit)?;
+            match symbol {
+                0..=255 => {
+                    self.room_for(1)?;
+                    self.out.push(symbol as u8);
+                }
+                256 => return Ok(()),
+                _ => {
+                    let i = usize::from(symbol - 257);
+                    if i >= LEN_BASE.len() {
+                        return Err("invalid length code".into());
+                    }
+                    let len = usize::from(LEN_BASE[i]) + self.bits(LEN_EXTRA[i])? as usize;
+                    let d = usize::from(self.decode(dist)?);
+                    if d >= DIST_BASE.len() {
+                        return Err("invalid distance code".into());
+                    }
+                    let distance = usize::from(DIST_BASE[d]) + self.bits(DIST_EXTRA[d])? as usize;
+                    if distance > self.out.len() {
+                        return Err("distance too far back".into());
+                    }
+                    self.room_for(len)?;
+                    // may overlap with what's being written
+                    let start = self.out.len() - distance;
+                    for i in 0..len {
+                        self.out.push(self.out[start + i]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The codes of blocks compressed with fixed Huffman codes
+fn fixed_codes() -> Result<(Huffman, Huffman), String> {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
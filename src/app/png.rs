@@ -0,0 +1,177 @@
+use super::checksum::crc32;
+
+/// Magic bytes at the start of every PNG file
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// Longest back-reference DEFLATE has
+const MAX_MATCH: usize = 258;
+/// Farthest back a DEFLATE back-reference can reach
+const WINDOW: usize = 32 << 10;
+/// Shortest back-reference DEFLATE has
+const MIN_MATCH: usize = 3;
+/// Smallest length of each length code, from 257
+const LEN_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+/// Extra bits after each length code
+const LEN_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// Smallest distance of each distance code
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits after each distance code
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// An 8-bit grayscale PNG of `width`x`height` from `pixels`, row by row.
+/// The image data is compressed with the fixed Huffman codes, repeating runs and rows.
+///
+/// # Panics
+///
+/// if `pixels` isn't `width * height` long
+pub fn encode_gray(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let row_len = width as usize;
+    assert_eq!(pixels.len(), row_len * height as usize, "pixels don't fit");
+
+    // every row starts with its filter type: 0, none
+    let mut raw = Vec::with_capacity((row_len + 1) * height as usize);
+    for row in pixels.chunks(row_len.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // bit depth, color type: grayscale, compression, filter, interlace
+    ihdr.extend_from_slice(&[8, 0, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &zlib(&raw, row_len + 1));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Append a chunk: length, type, data, then the CRC of type and data
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let len = u32::try_from(data.len()).expect("chunk too long");
+    png.extend_from_slice(&len.to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// `data` as a zlib stream of one DEFLATE block with the fixed Huffman codes.
+/// Back-references only look 1 byte back, for runs, and `stride` bytes back, for rows.
+fn zlib(data: &[u8], stride: usize) -> Vec<u8> {
+    // deflate, 32K window, no preset dictionary, check bits
+    let mut bits = Bits {
+        out: vec![0x78, 0x01],
+        ..Bits::default()
+    };
+    // final block, fixed Huffman codes
+    bits.push(1, 1);
+    bits.push(1, 2);
+    let match_len = |pos: usize, distance: usize| {
+        if distance == 0 || distance > pos || distance > WINDOW {
+            return 0;
+        }
+        data[pos..]
+            .iter()
+            .take(MAX_MATCH)
+            .zip(&data[pos - distance..])
+            .take_while(|(a, b)| a == b)
+            .count()
+    };
+    let mut pos = 0;
+    while pos < data.len() {
+        let (len, distance) = [1, stride]
+            .into_iter()
+            .map(|distance| (match_len(pos, distance), distance))
+            .max_by_key(|&(len, _)| len)
+            .expect("two distances");
+        if len < MIN_MATCH {
+            bits.literal(u16::from(data[pos]));
+            pos += 1;
+            continue;
+        }
+        bits.back_reference(len, distance);
+        pos += len;
+    }
+    // end of block
+    bits.literal(256);
+    let mut out = bits.finish();
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Writes DEFLATE's bit stream: least significant bit first
+#[derive(Default)]
+struct Bits {
+    out: Vec<u8>,
+    /// bits not written to `out` yet
+    acc: u32,
+    /// number of bits in `acc`
+    len: u8,
+}
+
+impl Bits {
+    /// Append the lowest `n` bits of `value`
+    fn push(&mut self, value: u32, n: u8) {
+        self.acc |= value << self.len;
+        self.len += n;
+        while self.len >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+    /// Append a Huffman `code` of `n` bits: those go most significant bit first
+    fn code(&mut self, code: u32, n: u8) {
+        self.push(code.reverse_bits() >> (32 - n), n);
+    }
+    /// Append a literal/length `symbol` in its fixed Huffman code
+    fn literal(&mut self, symbol: u16) {
+        let symbol = u32::from(symbol);
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xc0 + symbol - 280, 8),
+        }
+    }
+    /// Append a back-reference of `len` bytes from `distance` bytes back
+    fn back_reference(&mut self, len: usize, distance: usize) {
+        let i = LEN_BASE.partition_point(|&base| usize::from(base) <= len) - 1;
+        self.literal(257 + i as u16);
+        self.push((len - usize::from(LEN_BASE[i])) as u32, LEN_EXTRA[i]);
+        let i = DIST_BASE.partition_point(|&base| usize::from(base) <= distance) - 1;
+        self.code(i as u32, 5);
+        self.push((distance - usize::from(DIST_BASE[i])) as u32, DIST_EXTRA[i]);
+    }
+    /// The bytes written, the last one padded with zeros
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// Adler-32 checksum as used by zlib
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+    b << 16 | a
+}
@@ -0,0 +1,90 @@
+use crate::app::Cell;
+use ratatui::layout::Rect;
+use std::str::FromStr;
+
+/// Birth/survival rule, in `B3/S23` notation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// numbers of live neighbours making a dead cell alive
+    pub birth: Vec<u8>,
+    /// numbers of live neighbours keeping a live cell alive
+    pub survival: Vec<u8>,
+}
+/// Conway's: B3/S23
+impl Default for Rule {
+    fn default() -> Self {
+        Rule {
+            birth: vec![3],
+            survival: vec![2, 3],
+        }
+    }
+}
+
+/// A `Rule` used instead of the universe's own inside `region`, `x` being the column, `y` the row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegionRule {
+    pub region: Rect,
+    pub rule: Rule,
+}
+
+impl Rule {
+    /// Parse `B<digits>/S<digits>` notation, case-insensitively, eg.: `B36/S23`
+    ///
+    /// # Errors
+    ///
+    /// if `s` isn't in `B/S` notation, or a neighbour count isn't in `0..=8`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let err = |why: &str| format!("invalid rule {s:?}: {why}, should be like \"B3/S23\"");
+        let digits = |part: &str| -> Result<Vec<u8>, String> {
+            let mut ns = part
+                .chars()
+                .map(|ch| match ch.to_digit(10) {
+                    Some(n @ 0..=8) => Ok(n as u8),
+                    _ => Err(err(&format!("{ch:?} is not a neighbour count"))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            ns.sort_unstable();
+            ns.dedup();
+            Ok(ns)
+        };
+
+        let (mut birth, mut survival) = (None, None);
+        for part in s.trim().split('/') {
+            let part = part.trim();
+            match part.chars().next().map(|ch| ch.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => birth = Some(digits(&part[1..])?),
+                Some('S') if survival.is_none() => survival = Some(digits(&part[1..])?),
+                _ => return Err(err(&format!("unexpected {part:?}"))),
+            }
+        }
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Rule { birth, survival }),
+            _ => Err(err("both B and S are needed")),
+        }
+    }
+
+    /// The next state of `cell` with `live_neighbours`
+    pub fn next(&self, cell: Cell, live_neighbours: u8) -> Cell {
+        match cell {
+            // a dead cell with the right number of neighbours becomes alive, as if by reproduction
+            Cell::Dead => self.birth.contains(&live_neighbours).into(),
+            // a live cell with too few or too many neighbours dies, as if by under/overpopulation
+            Cell::Alive => self.survival.contains(&live_neighbours).into(),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = |ns: &[u8]| ns.iter().map(u8::to_string).collect::<String>();
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
@@ -2,36 +2,210 @@ use super::*;
 
 /// Number of special shapes supported
 pub const N: usize = 4;
+/// Index of `rand` among the special shapes
+pub const RAND: usize = 2;
 
 #[cfg(test)]
 mod tests;
 
+/// The built-in patterns, in the order of `all`
+const PATTERNS: [&str; 8] = [
+    GLIDER,
+    GOSPER_GLIDER_GUN,
+    COPPERHEAD,
+    RABBITS,
+    BONK_TIE,
+    ACORN,
+    SIR_ROBIN,
+    SNARK_LOOP,
+];
+
 pub fn all() -> Vec<Universe> {
-    vec![
-        Universe::from_str(GLIDER).unwrap(),
-        Universe::from_str(GOSPER_GLIDER_GUN).unwrap(),
-        Universe::from_str(COPPERHEAD).unwrap(),
-        Universe::from_str(RABBITS).unwrap(),
-        Universe::from_str(BONK_TIE).unwrap(),
-        Universe::from_str(ACORN).unwrap(),
-    ]
+    PATTERNS
+        .iter()
+        .map(|pattern| Universe::from_str(pattern).unwrap())
+        .collect()
+}
+
+/// Names of the patterns in `all`, in the same order, without parsing them
+pub fn names() -> Vec<&'static str> {
+    PATTERNS
+        .iter()
+        .map(|pattern| {
+            pattern
+                .lines()
+                .find_map(|line| line.strip_prefix("!Name:"))
+                .unwrap_or_default()
+                .trim()
+        })
+        .collect()
+}
+
+/// Find one of `all` by `name`, ignoring case, spaces and punctuation.
+/// A part of a name is enough if only one shape has it.
+///
+/// # Errors
+///
+/// if no shape or several shapes match, with the closest names
+pub fn find(name: &str) -> Result<Universe, String> {
+    let simplify = |s: &str| {
+        s.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let wanted = simplify(name);
+    let shapes = all();
+    let names = shapes
+        .iter()
+        .map(|u| simplify(u.name()))
+        .collect::<Vec<_>>();
+
+    if let Some(i) = names.iter().position(|n| *n == wanted) {
+        return Ok(shapes[i].clone());
+    }
+    let partial = (0..shapes.len())
+        .filter(|&i| !wanted.is_empty() && names[i].contains(&wanted))
+        .collect::<Vec<_>>();
+    let suggestions = match partial[..] {
+        [i] => return Ok(shapes[i].clone()),
+        [] => {
+            let distances = names.iter().map(|n| edit_distance(n, &wanted));
+            let closest = distances.clone().min().unwrap_or(0);
+            // too different to be a typo
+            if closest > wanted.len().max(3) / 2 {
+                return Err(format!(
+                    "unknown shape {name:?}, should be one of: {}",
+                    self::names().join(", ")
+                ));
+            }
+            distances
+                .enumerate()
+                .filter(|&(_, d)| d == closest)
+                .map(|(i, _)| i)
+                .collect()
+        }
+        _ => partial,
+    };
+    let suggestions = suggestions
+        .iter()
+        .map(|&i| format!("{:?}", shapes[i].name()))
+        .collect::<Vec<_>>();
+    Err(format!(
+        "unknown shape {name:?}, did you mean {}?",
+        suggestions.join(" or ")
+    ))
+}
+
+/// Levenshtein distance: the fewest characters to insert, delete or replace to get `b` from `a`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let replace = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = replace.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Names of the special shapes, in the order of `get_special`
+pub const SPECIAL_NAMES: [&str; N] = ["full", "frame", "random", "stripes"];
+
+/// One of the special shapes by its name, ignoring case, filling `area`,
+/// or one of `all` found by `find`, in the middle of `area`, grown if it doesn't fit
+///
+/// # Errors
+///
+/// if `find` doesn't find it
+pub fn get(name: &str, area: Area) -> Result<Universe, String> {
+    if let Some(i) = SPECIAL_NAMES
+        .iter()
+        .position(|special| special.eq_ignore_ascii_case(name.trim()))
+    {
+        return Ok(get_special(i, area));
+    }
+    let figur = find(name)?;
+    Universe::from_figur(area.union(figur.area), figur)
 }
 
 pub fn get_special(i: usize, area: Area) -> Universe {
     match i {
         0 => full(area),
         1 => frame(area),
-        2 => rand(area),
+        RAND => rand(area),
         3 => stripes(area),
         i => unreachable!("index out of bounds: len is {N} but index is {i}"),
     }
 }
 
+/// Tile `universes` into one big `Universe`: a grid as square as possible,
+/// every pattern cropped to its content and placed in the top-left corner of its slot,
+/// with `gutter` dead cells around and between the slots.
+pub fn zoo(universes: &[Universe], gutter: u16) -> Universe {
+    let figurs = universes
+        .iter()
+        .map(Universe::crop_to_content)
+        .collect::<Vec<_>>();
+    if figurs.is_empty() {
+        return empty(Area::default()).with_name("zoo");
+    }
+    let cols = (figurs.len() as f64).sqrt().ceil() as u16;
+    let rows = (figurs.len() as u16).div_ceil(cols);
+    let slot = Area::new(
+        figurs.iter().map(Universe::width).max().unwrap_or(0),
+        figurs.iter().map(Universe::height).max().unwrap_or(0),
+    );
+    let area = Area::new(
+        cols * slot.width + (cols + 1) * gutter,
+        rows * slot.height + (rows + 1) * gutter,
+    );
+
+    let mut univ = empty(area).with_name("zoo");
+    for (i, figur) in figurs.iter().enumerate() {
+        let (row, col) = (i as u16 / cols, i as u16 % cols);
+        univ.stamp(
+            figur,
+            gutter + row * (slot.height + gutter),
+            gutter + col * (slot.width + gutter),
+        );
+    }
+    univ
+}
+
+/// `rand_seeded` with a random seed, returned too, to get the same board again
+pub fn rand_with_seed(area: Area) -> (Universe, u64) {
+    let seed = fastrand::u64(..);
+    (rand_seeded(area, seed), seed)
+}
+
 pub fn rand(area: Area) -> Universe {
-    let cells = (0..area.len()).map(|_i| fastrand::bool().into()).collect();
+    rand_density(area, 0.5)
+}
+
+/// Random board with about `density` of the cells alive, clamped to `0.0..=1.0`
+pub fn rand_density(area: Area, density: f64) -> Universe {
+    rand_density_seeded(area, density, fastrand::u64(..))
+}
+
+/// Same as `rand_density`, but always the same for the same `seed`
+pub fn rand_density_seeded(area: Area, density: f64, seed: u64) -> Universe {
+    let density = density.clamp(0., 1.);
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let cells = (0..area.len())
+        .map(|_i| (rng.f64() < density).into())
+        .collect();
     Universe::new(area, cells, "random")
 }
 
+/// Same as `rand`, but always the same for the same `seed`
+pub fn rand_seeded(area: Area, seed: u64) -> Universe {
+    rand_density_seeded(area, 0.5, seed)
+}
+
 pub fn stripes(area: Area) -> Universe {
     let cells = (0..area.len())
         .map(|i| {
@@ -142,3 +316,149 @@ pub const ACORN: &str = "\
 .O
 ...O
 OO..OOO";
+
+/// 31×79, moves 2 cells up and 1 left every 6 generations
+pub const SIR_ROBIN: &str = "\
+!Name: Sir Robin
+!Author: Adam P. Goucher, Tom Rokicki
+!The first elementary knightship, found in 2018.
+!www.conwaylife.com/wiki/index.php?title=Sir_Robin
+....OO
+....O..O
+....O...O
+......OOO
+..OO......OOOO
+..O.OO....OOOO
+.O....O......OOO
+..OOOO....OO...O
+O.........OO
+.O...O
+......OOO..OO..O
+..OO.......O....O
+.............O.OO
+..........OO......O
+...........OO.OOO.O
+..........OO...O..O
+..........O.O..OO
+..........O..O.O.O
+..........OOO......O
+...........O.O.O...O
+..............OO.O.O
+...........O......OOO
+
+...........O.........O
+...........O...O......O
+............O.....OOOOO
+............OOO
+................OO
+.............OOO..O
+...........O.OOO.O
+..........O...O..O
+...........O....OO.OOO
+.............OOOO.O....OO
+.............O.OOOO....OO
+...................O
+....................O..OO
+....................OO
+.....................OOOOO
+.........................OO
+...................OOO......O
+....................O.O...O.O
+...................O...O...O
+...................O...OO
+..................O......O.OOO
+...................OO...O...OO
+....................OOOO..O..O
+......................OO...O
+.....................O
+.....................OO.O
+....................O
+...................OOOOO
+...................O....O
+..................OOO.OOO
+..................O.OOOOO
+..................O
+....................O
+................O....OOOO
+....................OOOO.OO
+.................OOO....O
+........................O.O
+............................O
+........................O..OO
+.........................OOO
+......................OO
+.....................OOO.....O
+........................OO..O.O
+.....................O..OOO.O.O
+......................OO.O..O
+........................O.O..OO
+..........................OO
+......................OOO....O
+......................OOO....O
+.......................OO...OOO
+........................OO.OO
+.........................OO
+.........................O
+
+........................OO
+..........................O";
+
+/// 54×54, a glider bounced around by four snarks, period 256
+pub const SNARK_LOOP: &str = "\
+!Name: Snark loop
+!A glider reflected by four snarks, returning to its start every 256 generations.
+!www.conwaylife.com/wiki/index.php?title=Snark
+.............................OO
+............................O.O
+......................OO....O
+....................O..O..OO.OOOO
+....................OO.O.O.O.O..O
+.......................O.O.O.O
+.......................O.O.OO
+........................O
+
+.....................................OO
+............................OO.......O
+............................OO.....O.O
+.........O.........................OO
+.........OOO
+............O
+...........OO
+
+
+...OO
+...O.....................OO
+OO.O......................O......OOO
+O..OOO....OO...........OOO.......O
+.OO...O...OO...........O..........O
+...OOOO..........................................OO
+...O...............OO............................O
+....OOO............O.O.............................O
+.......O.............O.........OO..............OOOOO
+..OOOOO..............OO.........O.............O
+..O.............................O.O............OOO
+....O............................OO...............O
+...OO..........................................OOOO
+..............................O...........OO...O...OO
+............................OOO...........OO....OOO..O
+...........................O......................O.OO
+...........................OO.....................O
+.................................................OO
+
+
+.........................................OO
+.........................................O
+..........................................OOO
+.................OO.........................O
+................O.O.....OO
+................O.......OO
+...............OO
+
+.............................O
+.........................OO.O.O
+........................O.O.O.O
+.....................O..O.O.O.O.OO
+.....................OOOO.OO..O..O
+.........................O....OO
+.......................O.O
+.......................OO";
@@ -168,3 +168,177 @@ fn full_test() {
         }
     }
 }
+
+#[test]
+fn zoo_test() {
+    let glider = Universe::from_str(GLIDER).unwrap();
+    let bonk_tie = Universe::from_str(BONK_TIE).unwrap();
+    let blinker = Universe::from_str("...\nOOO\n...").unwrap();
+    let zoo = zoo(&[glider.clone(), bonk_tie.clone(), blinker], 1);
+
+    // 2x2 grid of 3x5 slots, 1 wide gutters
+    assert_eq!(zoo.area, Area::new(2 * 3 + 3, 2 * 5 + 3));
+    let exp = Universe::from_str(
+        "\
+.........
+...O.OO..
+.O.O.OO..
+..OO...O.
+.......O.
+.......O.
+.........
+.OOO.....
+.........
+.........
+.........
+.........
+.........",
+    )
+    .unwrap()
+    .with_name("zoo");
+    print!("{zoo}");
+    assert_eq!(zoo, exp);
+}
+
+#[test]
+fn find_exact() {
+    assert_eq!(find("Glider").unwrap().name(), "Glider");
+    assert_eq!(
+        find("gosper glider gun").unwrap().name(),
+        "Gosper glider gun"
+    );
+    assert_eq!(find("bonk-tie").unwrap().name(), "Bonk tie");
+    // "glider" is also part of "Gosper glider gun", but it's an exact match
+    assert_eq!(find("GLIDER").unwrap().name(), "Glider");
+}
+#[test]
+fn find_partial() {
+    assert_eq!(find("gosper").unwrap().name(), "Gosper glider gun");
+    assert_eq!(find("glidergun").unwrap().name(), "Gosper glider gun");
+    assert_eq!(find("copper").unwrap().name(), "Copperhead");
+}
+#[test]
+fn find_near_miss() {
+    assert_eq!(
+        find("glidr").unwrap_err(),
+        "unknown shape \"glidr\", did you mean \"Glider\"?"
+    );
+    assert_eq!(
+        find("rabits").unwrap_err(),
+        "unknown shape \"rabits\", did you mean \"Rabbits\"?"
+    );
+    assert!(find("spaceship")
+        .unwrap_err()
+        .starts_with("unknown shape \"spaceship\", should be one of: Glider, "));
+}
+#[test]
+fn find_ambiguous() {
+    // in "Rabbits", "Bonk tie" and "Sir Robin"
+    let err = find("b").unwrap_err();
+    assert_eq!(
+        err,
+        "unknown shape \"b\", did you mean \"Rabbits\" or \"Bonk tie\" or \"Sir Robin\"?"
+    );
+}
+#[test]
+fn edit_distances() {
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("glider", "glider"), 0);
+}
+#[test]
+fn find_missing() {
+    // well known, but not built in: an error, not a panic
+    for name in ["two engine cordership", "gemini"] {
+        let err = find(name).unwrap_err();
+        assert!(err.starts_with(&format!("unknown shape {name:?}")), "{err}");
+    }
+    for shape in all() {
+        assert!(shape.population() > 0, "{} is empty", shape.name());
+    }
+}
+#[test]
+fn sir_robin() {
+    let robin = find("sir robin").unwrap();
+    assert_eq!(robin.population(), 282);
+    assert_eq!(robin.crop_to_content(), robin);
+
+    let mut univ = Universe::from_figur(Area::new(64, 120), robin.clone()).unwrap();
+    let start = univ.bounding_box().unwrap();
+    for _ in 0..6 {
+        univ.tick();
+    }
+    let moved = univ.bounding_box().unwrap();
+    // 2 up, 1 left, same shape
+    assert_eq!((start.y - moved.y, start.x - moved.x), (2, 1));
+    assert_eq!(univ.crop_to_content(), robin);
+}
+#[test]
+fn snark_loop() {
+    let snarks = find("snark loop").unwrap();
+    assert_eq!(snarks.population(), 4 * 49 + 5);
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+        let mut univ = Universe::from_figur(Area::new(64, 64), snarks.clone()).unwrap();
+        univ.set_boundary(boundary);
+        assert_eq!(univ.detect_period(256), Some(256), "{boundary:?}");
+    }
+}
+#[test]
+fn get_by_name() {
+    let area = Area::new(40, 30);
+    for (i, name) in SPECIAL_NAMES.into_iter().enumerate() {
+        let univ = get(&name.to_uppercase(), area).unwrap();
+        assert_eq!(univ.area, area);
+        if i != RAND {
+            assert_eq!(univ, get_special(i, area));
+        }
+    }
+    assert_eq!(get("stripes", area).unwrap().name(), "stripes");
+
+    let glider = get("Glider", area).unwrap();
+    assert_eq!(glider.area, area);
+    assert_eq!(glider.crop_to_content(), find("glider").unwrap());
+    // grown to fit
+    assert_eq!(
+        get("gosper", Area::new(8, 8)).unwrap().area,
+        Area::new(36, 9)
+    );
+    assert!(get("cordership", area).is_err());
+}
+#[test]
+fn names_match() {
+    let shapes = all();
+    assert_eq!(names().len(), shapes.len());
+    for (name, shape) in names().into_iter().zip(&shapes) {
+        assert_eq!(name, shape.name());
+        assert_eq!(find(name).unwrap(), *shape);
+    }
+    assert_eq!(SPECIAL_NAMES.len(), N);
+}
+#[test]
+fn rand_reproducible() {
+    let area = Area::new(30, 20);
+    assert_eq!(rand_seeded(area, 7), rand_seeded(area, 7));
+    assert_ne!(rand_seeded(area, 7).cells, rand_seeded(area, 8).cells);
+    let (univ, seed) = rand_with_seed(area);
+    assert_eq!(univ, rand_seeded(area, seed));
+    assert_eq!(univ.name(), "random");
+}
+#[test]
+fn rand_density_observed() {
+    let area = Area::new(200, 200);
+    for density in [0.1, 0.5, 0.8] {
+        let observed = rand_density(area, density).population() as f64 / area.len() as f64;
+        assert!(
+            (observed - density).abs() < 0.02,
+            "{observed} isn't {density}"
+        );
+    }
+    assert_eq!(rand_density(area, -1.).population(), 0);
+    assert_eq!(rand_density(area, 2.).population(), area.len());
+
+    let sparse = rand_density_seeded(area, 0.1, 7);
+    assert_eq!(sparse, rand_density_seeded(area, 0.1, 7));
+    assert_ne!(sparse.cells, rand_density_seeded(area, 0.1, 8).cells);
+    assert_eq!(rand_density_seeded(area, 0.5, 7), rand_seeded(area, 7));
+}
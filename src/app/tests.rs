@@ -1,4 +1,5 @@
 use super::*;
+use ratatui::layout::Rect;
 
 fn gen_uni(area: Area, cells: &[bool]) -> Universe {
     let cells = cells.iter().map(|c| (*c).into()).collect::<Vec<Cell>>();
@@ -409,3 +410,2790 @@ fn neighbours() {
     assert_eq!(nghbrs((3, 2)), 3);
     assert_eq!(nghbrs((3, 3)), 3);
 }
+
+#[test]
+fn cell_ops() {
+    use Cell::{Alive, Dead};
+
+    assert_eq!(!Dead, Alive);
+    assert_eq!(!Alive, Dead);
+
+    let table = [(Dead, Dead), (Dead, Alive), (Alive, Dead), (Alive, Alive)];
+    let or = table.map(|(a, b)| a | b);
+    let and = table.map(|(a, b)| a & b);
+    let xor = table.map(|(a, b)| a ^ b);
+    assert_eq!(or, [Dead, Alive, Alive, Alive]);
+    assert_eq!(and, [Dead, Dead, Dead, Alive]);
+    assert_eq!(xor, [Dead, Alive, Alive, Dead]);
+}
+
+#[test]
+fn longevity() {
+    let area = Area::new(6, 6);
+    let fig = |s: &str| Universe::from_figur(area, Universe::from_str(s).unwrap()).unwrap();
+
+    // becomes a block
+    let lng = fig("OO\nO.").measure_longevity(100);
+    let exp = Longevity {
+        generations: 1,
+        period: Some(1),
+        peak_population: 4,
+        final_population: 4,
+    };
+    assert_eq!(lng, exp);
+
+    // dies out
+    let lng = fig("O.\n.O").measure_longevity(100);
+    let exp = Longevity {
+        generations: 1,
+        period: Some(1),
+        peak_population: 2,
+        final_population: 0,
+    };
+    assert_eq!(lng, exp);
+
+    // blinker
+    let lng = fig("OOO").measure_longevity(100);
+    let exp = Longevity {
+        generations: 0,
+        period: Some(2),
+        peak_population: 3,
+        final_population: 3,
+    };
+    assert_eq!(lng, exp);
+
+    // glider doesn't come back in time
+    let lng = fig(shapes::GLIDER).measure_longevity(10);
+    let exp = Longevity {
+        generations: 10,
+        period: None,
+        peak_population: 5,
+        final_population: 5,
+    };
+    assert_eq!(lng, exp);
+}
+
+#[test]
+fn stamp_checked() {
+    let block = Universe::from_str("OO\nOO").unwrap();
+    let mut univ = shapes::empty(Area::new(6, 6));
+
+    assert_eq!(univ.stamp_checked(&block, 0, 0), Ok(()));
+    assert_eq!(univ.stamp_checked(&block, 3, 3), Ok(()));
+    let before = univ.clone();
+    assert_eq!(
+        univ.stamp_checked(&block, 1, 1),
+        Err(Overlap { row: 1, col: 1 })
+    );
+    assert_eq!(univ, before);
+    assert_eq!(univ.population(), 8);
+
+    // dead cells may overlap
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut univ = shapes::empty(Area::new(6, 6));
+    univ.stamp_checked(&glider, 0, 0).unwrap();
+    let domino = Universe::from_str("OO").unwrap();
+    assert_eq!(univ.stamp_checked(&domino, 0, 0), Ok(()));
+}
+
+#[test]
+fn toggle_command() {
+    let area = Area::new(4, 4);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+
+    app.execute("toggle 0,0 1,2 3,3").unwrap();
+    app.execute("t 3,3").unwrap();
+    let exp = Universe::from_str("O...\n..O.\n....\n....")
+        .unwrap()
+        .with_name("empty");
+    assert_eq!(app.universe, exp);
+
+    assert!(app.execute("toggle 4,0").is_err());
+    assert!(app.execute("toggle 1;1").is_err());
+    assert!(app.execute("toggle").is_err());
+    assert!(app.execute("nonsense 0,0").is_err());
+    // nothing happens on error, not even partially
+    assert!(app.execute("toggle 1,1 9,9").is_err());
+    assert_eq!(app.universe, exp);
+}
+
+#[test]
+fn watermark_text() {
+    assert_eq!(ui::watermark(0, None), " gen: 0 ");
+    assert_eq!(ui::watermark(1234, Some(56)), " gen: 1234, pop: 56 ");
+
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut univ = Universe::from_figur(Area::new(6, 6), glider).unwrap();
+    univ.tick();
+    univ.tick();
+    assert_eq!(
+        ui::watermark(univ.generation(), Some(univ.population())),
+        " gen: 2, pop: 5 "
+    );
+}
+
+#[test]
+fn live_ratio() {
+    let half = Universe::from_str("OO..\nO.O.\n.O.O").unwrap();
+    assert!((half.live_ratio() - 0.5).abs() < f64::EPSILON);
+    assert_eq!(shapes::empty(Area::new(4, 4)).live_ratio(), 0.0);
+    assert_eq!(shapes::full(Area::new(4, 4)).live_ratio(), 1.0);
+    assert_eq!(Universe::default().live_ratio(), 0.0);
+}
+
+#[test]
+fn auto_slow() {
+    let area = Area::new(8, 8);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+    app.auto_slow = true;
+    app.event_threshold = 10;
+
+    app.on_change(10);
+    assert_eq!(app.effective_poll_t(), DEF_DUR);
+    app.on_change(11);
+    assert_eq!(app.effective_poll_t(), DEF_DUR * SLOW_FACTOR);
+    for _ in 1..SLOW_GENS {
+        app.on_change(0);
+        assert_eq!(app.effective_poll_t(), DEF_DUR * SLOW_FACTOR);
+    }
+    app.on_change(0);
+    assert_eq!(app.effective_poll_t(), DEF_DUR);
+
+    // not while paused
+    app.on_change(11);
+    app.play_pause();
+    assert_eq!(app.effective_poll_t(), PAUSE);
+
+    // a full board dies all at once
+    let mut app = App::new(area, vec![shapes::full(area)], DEF_DUR);
+    app.auto_slow = true;
+    app.tick();
+    assert!(app.slowed_down());
+}
+
+#[test]
+fn parse_empty() {
+    assert!(Universe::from_str("").is_err());
+    assert!(Universe::from_str(" \n\n  \n").is_err());
+    assert!(Universe::from_str("!Name: nothing\n!just comments").is_err());
+    assert!(Universe::from_str("!Name: something\n.").is_ok());
+}
+
+#[test]
+fn rule_parse() {
+    assert_eq!(Rule::parse("B3/S23"), Ok(Rule::default()));
+    assert_eq!(Rule::parse("s23/b3"), Ok(Rule::default()));
+    let day_and_night = Rule::parse("B3678/S34678").unwrap();
+    assert_eq!(day_and_night.birth, [3, 6, 7, 8]);
+    assert_eq!(day_and_night.survival, [3, 4, 6, 7, 8]);
+    assert_eq!(day_and_night.to_string(), "B3678/S34678");
+    assert_eq!(Rule::parse("B2/S").unwrap().to_string(), "B2/S");
+
+    assert!(Rule::parse("B9/SX").is_err());
+    assert!(Rule::parse("B3").is_err());
+    assert!(Rule::parse("B3/S23/B3").is_err());
+    assert!(Rule::parse("23/3").is_err());
+    assert!(Rule::parse("").is_err());
+}
+
+#[test]
+fn apply_rule_string() {
+    // the middle one has 6 neighbours
+    let figur = Universe::from_str("OOO\nO.O\nO..").unwrap();
+    let mut conway = Universe::from_figur(Area::new(7, 7), figur).unwrap();
+    let mut highlife = conway.clone();
+
+    assert!(highlife.apply_rule_string("B3/SX").is_err());
+    assert_eq!(highlife.rule(), &Rule::default());
+    highlife.apply_rule_string("B36/S23").unwrap();
+    assert_eq!(highlife.rule().to_string(), "B36/S23");
+
+    conway.tick();
+    highlife.tick();
+    assert_eq!(conway[(3u8, 3u8)], Cell::Dead);
+    assert_eq!(highlife[(3u8, 3u8)], Cell::Alive);
+    highlife[(3u8, 3u8)] = Cell::Dead;
+    assert_eq!(conway, highlife);
+
+    let mut app = App::new(Area::new(7, 7), vec![conway], DEF_DUR);
+    app.execute("rule B36/S23").unwrap();
+    assert_eq!(app.universe.rule().to_string(), "B36/S23");
+    assert!(app.execute("rule B36").is_err());
+}
+
+#[test]
+fn void_border() {
+    let mut univ = shapes::empty(Area::new(4, 3));
+    let voids = |univ: &Universe| {
+        (0..univ.height())
+            .flat_map(|row| (0..univ.width()).map(move |col| (row, col)))
+            .filter(|&(row, col)| univ.is_void(row, col))
+            .collect::<Vec<_>>()
+    };
+    assert!(voids(&univ).is_empty());
+
+    univ.set_boundary(BoundaryMode::Dead);
+    #[rustfmt::skip]
+    let exp = [
+        (0, 0), (0, 1), (0, 2), (0, 3),
+        (1, 0),                 (1, 3),
+        (2, 0), (2, 1), (2, 2), (2, 3),
+    ];
+    assert_eq!(voids(&univ), exp);
+}
+
+#[test]
+fn to_rle() {
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    assert_eq!(
+        glider.to_rle(),
+        "#N Glider\nx = 3, y = 3, rule = B3/S23\n2bo$obo$b2o!\n"
+    );
+
+    let gappy = Universe::from_str("....\n.O..\n....\n....\nO..O\n....")
+        .unwrap()
+        .with_name("");
+    assert_eq!(gappy.to_rle(), "x = 4, y = 6, rule = B3/S23\n$bo3$o2bo!\n");
+    assert_eq!(
+        shapes::empty(Area::new(3, 3)).to_rle(),
+        "#N empty\nx = 3, y = 3, rule = B3/S23\n!\n"
+    );
+
+    // long lines are wrapped
+    let stripes = shapes::stripes(Area::new(100, 1));
+    assert!(stripes.to_rle().lines().all(|line| line.len() <= 70));
+}
+
+#[test]
+fn export_rle_frames() {
+    let dir = std::env::temp_dir().join(format!("cgol-tui-rle-frames-{}", std::process::id()));
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut univ = Universe::from_figur(Area::new(10, 10), glider).unwrap();
+
+    assert!(univ.export_rle_frames(&dir, 8, 0).is_err());
+    assert_eq!(univ.export_rle_frames(&dir, 8, 3).unwrap(), 3);
+    assert_eq!(univ.generation(), 8);
+
+    let mut files = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(files, ["gen_00000.rle", "gen_00003.rle", "gen_00006.rle"]);
+    for file in files {
+        let rle = std::fs::read_to_string(dir.join(file)).unwrap();
+        assert!(rle.starts_with("#N Glider\nx = 3, y = 3, rule = B3/S23\n"));
+        assert!(rle.ends_with("!\n"));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn from_rle_headers() {
+    let glider = Universe::from_str(".O.\n..O\nOOO")
+        .unwrap()
+        .with_name("Glider");
+    let inputs = [
+        "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!",
+        "#N Glider\n#C missing rule\nx = 3, y = 3\nbo$2bo$3o!",
+        "#N Glider\nx=3,y=3,rule=b3/s23\nbo$2bo$3o!",
+        "#N Glider\n  x   =   3 ,  y =  3 ,  rule  =  B3/S23  \nbo$2bo$\n3o!",
+        "#N Glider\ny = 3, x = 3, rule = 23/3,\nbo$2bo$3o!",
+        "#N Glider\nrule = B3/S23\nbo$2bo$3o!",
+        "#N Glider\nbo$2bo$3o",
+    ];
+    for input in inputs {
+        let univ = Universe::from_rle(input).unwrap();
+        assert_eq!(univ, glider, "{input}");
+        assert_eq!(univ.name(), "Glider", "{input}");
+        assert_eq!(univ.rule(), &Rule::default(), "{input}");
+    }
+
+    let highlife = Universe::from_rle("x = 1, y = 1, rule = 23/36\no!").unwrap();
+    assert_eq!(highlife.rule().to_string(), "B36/S23");
+    // declared size is kept
+    let padded = Universe::from_rle("x = 5, y = 4\n$bo!").unwrap();
+    assert_eq!(padded.area, Area::new(5, 4));
+    assert_eq!(padded[(1u8, 1u8)], Cell::Alive);
+
+    assert!(Universe::from_rle("x = 2, y = 2\n3o!").is_err());
+    assert!(Universe::from_rle("x = 3, y = 3\nbz!").is_err());
+    assert!(Universe::from_rle("x = three, y = 3\n3o!").is_err());
+    assert!(Universe::from_rle("x = 3, y = 3, rule = B9/S\n3o!").is_err());
+    assert!(Universe::from_rle("").is_err());
+    assert!(Universe::from_rle("#N nothing\n#C at all").is_err());
+}
+
+#[test]
+fn sub_universe() {
+    let univ = Universe::from_str(
+        "\
+O.O.O
+.O.O.
+OO..O
+...OO",
+    )
+    .unwrap();
+
+    let sub = univ.sub_universe(Rect::new(1, 1, 3, 2));
+    let exp = Universe::from_str("O.O\nO..").unwrap();
+    assert_eq!(sub, exp);
+    for row in 0..2u16 {
+        for col in 0..3u16 {
+            assert_eq!(sub[(row, col)], univ[(row + 1, col + 1)]);
+        }
+    }
+
+    // clipped
+    let sub = univ.sub_universe(Rect::new(3, 2, 10, 10));
+    assert_eq!(sub, Universe::from_str(".O\nOO").unwrap());
+    let sub = univ.sub_universe(Rect::new(7, 7, 2, 2));
+    assert!(sub.area.is_empty());
+}
+
+#[test]
+fn ascii_dump() {
+    let univ = Universe::from_str("!Name: dump\n.O.\n..O\nOOO").unwrap();
+    assert_eq!(format!("{univ:#}"), "!Name: dump\n.O.\n..O\nOOO\n");
+    // can be read back
+    let read = Universe::from_str(&format!("{univ:#}")).unwrap();
+    assert_eq!(read, univ);
+    assert_eq!(read.name(), "dump");
+
+    let path = std::env::temp_dir().join(format!("cgol-tui-dump-{}.log", std::process::id()));
+    let area = Area::new(5, 5);
+    let univ = Universe::from_figur(area, univ).unwrap();
+    let mut app = App::new(area, vec![univ], DEF_DUR);
+    app.dump_path = path.clone();
+    app.dump().unwrap();
+    app.tick();
+    app.dump().unwrap();
+    let dumped = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        dumped,
+        "\
+!Name: dump
+.....
+..O..
+...O.
+.OOO.
+.....
+!Generation: 0
+
+!Name: dump
+.....
+.....
+.O.O.
+..OO.
+..O..
+!Generation: 1
+
+"
+    );
+}
+
+#[test]
+fn auto_restart() {
+    let area = Area::new(8, 8);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+
+    app.tick();
+    assert_eq!(app.universe.population(), 0);
+
+    app.auto_restart = true;
+    for _ in 0..=RESTART_DELAY {
+        app.tick();
+        assert_eq!(app.universe.population(), 0);
+        assert_eq!(app.seed, None);
+    }
+    app.tick();
+    let seed = app.seed.unwrap();
+    assert_eq!(app.universe, shapes::rand_seeded(area, seed));
+}
+
+#[test]
+fn auto_restart_oscillating() {
+    let area = Area::new(8, 8);
+    let blinker = Universe::from_figur(area, Universe::from_str("OOO").unwrap()).unwrap();
+    let mut app = App::new(area, vec![blinker], DEF_DUR);
+    app.restart();
+    app.auto_restart = true;
+
+    // the cycle is noticed after a period, then the countdown starts
+    for _ in 0..=RESTART_DELAY + 1 {
+        app.tick();
+        assert_eq!(app.seed, None);
+    }
+    app.tick();
+    let seed = app.seed.unwrap();
+    assert_eq!(app.universe, shapes::rand_seeded(area, seed));
+}
+
+#[test]
+fn tick_outcome() {
+    let area = Area::new(6, 6);
+    let fig = |s: &str| Universe::from_figur(area, Universe::from_str(s).unwrap()).unwrap();
+    assert_eq!(fig("OO\nOO").tick(), TickOutcome::Stable);
+    assert_eq!(fig("OOO").tick(), TickOutcome::Changed);
+    assert_eq!(fig("O").tick(), TickOutcome::Extinct);
+}
+
+#[test]
+fn fill_command() {
+    let area = Area::new(6, 5);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+
+    app.execute("fill 3,4 1,1").unwrap();
+    for row in 0..area.height {
+        for col in 0..area.width {
+            let inside = (1..=3).contains(&row) && (1..=4).contains(&col);
+            assert_eq!(app.universe[(row, col)], inside.into(), "({row};{col})");
+        }
+    }
+
+    app.execute("fill 2,2 2,3 dead").unwrap();
+    app.execute("fill 0,5 0,5 alive").unwrap();
+    let exp = Universe::from_str(
+        "\
+.....O
+.OOOO.
+.O..O.
+.OOOO.
+......",
+    )
+    .unwrap()
+    .with_name("empty");
+    assert_eq!(app.universe, exp);
+
+    assert!(app.execute("fill 0,0 5,0").is_err());
+    assert!(app.execute("fill 0,0").is_err());
+    assert!(app.execute("fill 0,0 1,1 zombie").is_err());
+    assert_eq!(app.universe, exp);
+}
+
+#[test]
+fn seed_reproduces() {
+    let area = Area::new(10, 8);
+    let mut app = App::default().with_seed(1234);
+    app.area = area;
+    app.restart();
+    assert_eq!(app.seed(), Some(1234));
+    assert_eq!(app.universe, shapes::rand_seeded(area, 1234));
+
+    // a new one on restart
+    app.restart();
+    let seed = app.seed().unwrap();
+    let board = app.universe.clone();
+    let mut replay = App::default().with_seed(seed);
+    replay.area = area;
+    replay.restart();
+    assert_eq!(replay.universe, board);
+
+    // not random: no seed
+    app.next();
+    assert_eq!(app.seed(), None);
+}
+
+#[test]
+fn diff_count() {
+    let blinker = Universe::from_str(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    let mut ticked = blinker.clone();
+    ticked.tick();
+    assert_eq!(blinker.diff_count(&blinker), 0);
+    // 2 die, 2 are born
+    assert_eq!(blinker.diff_count(&ticked), 4);
+    assert_eq!(ticked.diff_count(&blinker), 4);
+}
+
+#[test]
+#[should_panic(expected = "different areas")]
+fn diff_count_areas() {
+    shapes::empty(Area::new(2, 3)).diff_count(&shapes::empty(Area::new(3, 2)));
+}
+
+#[test]
+fn glyph_presets() {
+    let pairs = Glyphs::ALL.map(Glyphs::pair);
+    assert_eq!(pairs, [("◼", "◻"), ("●", "○"), ("█", "░"), ("🟩", "⬛")]);
+    for glyphs in Glyphs::ALL {
+        assert_eq!(glyphs.name().parse(), Ok(glyphs));
+        for cell in [Cell::Alive, Cell::Dead] {
+            let drawn = glyphs.cell(cell);
+            assert_eq!(ratatui::text::Span::raw(&drawn).width(), 2, "{drawn:?}");
+        }
+    }
+    assert_eq!(Glyphs::Blocks.cell(Cell::Alive), "█ ");
+    assert_eq!(Glyphs::Emoji.cell(Cell::Alive), "🟩");
+    assert_eq!("EMOJI".parse(), Ok(Glyphs::Emoji));
+    assert!("nope".parse::<Glyphs>().is_err());
+
+    let mut app = App::default();
+    let mut seen = vec![app.glyphs];
+    for _ in 0..Glyphs::ALL.len() {
+        app.next_glyphs();
+        seen.push(app.glyphs);
+    }
+    assert_eq!(
+        seen,
+        [None]
+            .into_iter()
+            .chain(Glyphs::ALL.map(Some))
+            .collect::<Vec<_>>()
+    );
+    app.next_glyphs();
+    assert_eq!(app.glyphs, None);
+}
+
+#[test]
+fn components() {
+    let univ = Universe::from_str(
+        "\
+OO....O
+O.....O
+...O...
+....O..
+.......
+OO...OO",
+    )
+    .unwrap();
+    let components = univ.components();
+    assert_eq!(
+        components,
+        [
+            vec![(0, 0), (0, 1), (1, 0)],
+            vec![(0, 6), (1, 6)],
+            vec![(2, 3), (3, 4)],
+            vec![(5, 0), (5, 1)],
+            vec![(5, 5), (5, 6)],
+        ]
+    );
+    assert_eq!(Universe::center_of_mass(&components[2]), Some((2.5, 3.5)));
+    assert_eq!(Universe::center_of_mass(&[]), None);
+}
+
+#[test]
+fn trace_glider() {
+    let area = Area::new(12, 12);
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut app = App::new(
+        area,
+        vec![Universe::from_figur(area, glider).unwrap()],
+        DEF_DUR,
+    );
+    app.toggle_trace();
+    for _ in 0..8 {
+        app.tick();
+    }
+    let path = &app.trace.as_ref().unwrap().path;
+    assert_eq!(path.len(), 9);
+    // a glider moves one cell diagonally in 4 generations
+    let ((r0, c0), (r4, c4), (r8, c8)) = (path[0], path[4], path[8]);
+    assert_eq!((r4 - r0, c4 - c0), (1., 1.));
+    assert_eq!((r8 - r4, c8 - c4), (1., 1.));
+
+    app.toggle_trace();
+    assert_eq!(app.trace, None);
+}
+
+#[test]
+fn config_log() {
+    let area = Area::new(8, 8);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+
+    app.execute("rule B36/S23").unwrap();
+    assert!(app.execute("rule nonsense").is_err());
+    app.toggle_boundary();
+    app.resize(Area::new(16, 10));
+    app.toggle_boundary();
+    assert_eq!(
+        app.log_entries().collect::<Vec<_>>(),
+        [
+            "rule → B36/S23",
+            "topology → bounded",
+            "size → 16x10",
+            "topology → toroidal"
+        ]
+    );
+
+    for i in 0..LOG_LEN {
+        app.resize(Area::new(10 + i as u16, 10));
+    }
+    assert_eq!(app.log_entries().count(), LOG_LEN);
+    assert_eq!(app.log_entries().next().unwrap(), "size → 10x10");
+}
+
+#[test]
+fn fingerprint_sequence() {
+    let area = Area::new(16, 16);
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let centered = Universe::from_figur(area, glider.clone()).unwrap();
+    let mut cornered = shapes::empty(area);
+    cornered.stamp(&glider, 1, 2);
+
+    let fingerprint = centered.fingerprint_sequence(9);
+    assert_eq!(fingerprint.len(), 9);
+    assert_eq!(fingerprint, centered.fingerprint_sequence(9));
+    assert_eq!(fingerprint, cornered.fingerprint_sequence(9));
+    // period 4
+    assert_eq!(fingerprint[..5], fingerprint[4..]);
+    assert_ne!(fingerprint[0], fingerprint[1]);
+    // not mutated
+    assert_eq!(centered.generation(), 0);
+
+    let blinker = Universe::from_figur(area, Universe::from_str("OOO").unwrap()).unwrap();
+    let blinker_fingerprint = blinker.fingerprint_sequence(9);
+    assert_eq!(blinker_fingerprint[0], blinker_fingerprint[2]);
+    assert_ne!(blinker_fingerprint[0], blinker_fingerprint[1]);
+    assert!(blinker_fingerprint.iter().all(|f| !fingerprint.contains(f)));
+}
+
+#[test]
+fn square_area() {
+    let fits = Area::new(120, 40);
+    assert_eq!(ui::fit_area(fits, false), fits);
+    assert_eq!(ui::fit_area(fits, true), Area::new(40, 40));
+    assert_eq!(ui::fit_area(Area::new(7, 9), true), Area::new(7, 7));
+}
+
+#[test]
+fn resize_keeps_pattern() {
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut app = App::new(Area::new(8, 8), vec![glider.clone()], DEF_DUR);
+    app.restart();
+    let before = app.universe.crop_to_content();
+
+    app.resize(Area::new(20, 12));
+    assert_eq!(app.universe.area, Area::new(20, 12));
+    assert_eq!(app.universe.crop_to_content().cells, before.cells);
+
+    app.resize(Area::new(6, 6));
+    assert_eq!(app.universe.area, Area::new(6, 6));
+    assert_eq!(app.universe.crop_to_content().cells, before.cells);
+}
+
+#[test]
+fn resize_keeps_random_board() {
+    let mut app = App::default().with_seed(42);
+    app.resize(Area::new(16, 16));
+    assert_eq!(app.seed(), Some(42));
+    for _ in 0..3 {
+        app.tick();
+    }
+    let before = app.universe.clone();
+
+    app.resize(Area::new(20, 20));
+    assert_eq!(app.universe.area, Area::new(20, 20));
+    assert_eq!(app.universe.generation(), before.generation());
+    assert_eq!(app.universe.population(), before.population());
+    // centered in dead margins: shrinking back gives the old board
+    let mut shrunk = app.universe.clone();
+    shrunk.resize(Area::new(16, 16));
+    assert_eq!(shrunk, before);
+    assert_eq!(app.seed(), None);
+}
+
+#[test]
+fn clipped_canvas() {
+    let canvas = Area::new(40, 20);
+    assert_eq!(ui::clipped(Area::new(40, 20), canvas), None);
+    assert_eq!(ui::clipped(Area::new(10, 5), canvas), None);
+    assert_eq!(
+        ui::clipped(Area::new(128, 128), canvas),
+        Some(Area::new(40, 20))
+    );
+    // only one side too big
+    assert_eq!(
+        ui::clipped(Area::new(30, 50), canvas),
+        Some(Area::new(30, 20))
+    );
+}
+
+#[test]
+fn pattern_bigger_than_canvas() {
+    let big = shapes::full(Area::new(30, 5));
+    let mut app = App::new(Area::new(10, 10), vec![big], DEF_DUR);
+    app.restart();
+    assert_eq!(app.universe.area, Area::new(30, 10));
+    assert_eq!(app.universe.population(), 150);
+
+    app.resize(Area::new(8, 8));
+    assert_eq!(app.universe.area, Area::new(30, 8));
+    assert_eq!(app.universe.population(), 150);
+}
+
+#[test]
+fn region_rules() {
+    // a HighLife replicator seed: births on 6 neighbours make a difference
+    let seed = Universe::from_str(
+        "\
+.OOO
+O...
+O...
+O...",
+    )
+    .unwrap();
+    let area = Area::new(16, 16);
+    let mut conway = Universe::from_figur(area, seed.clone()).unwrap();
+    let mut highlife = conway.clone();
+    highlife.apply_rule_string("B36/S23").unwrap();
+
+    // left half Conway, right half HighLife, far enough apart not to interact
+    let mut both = shapes::empty(Area::new(34, 16));
+    both.stamp(&conway, 0, 0);
+    both.stamp(&highlife, 0, 18);
+    both.add_region_rule(Rect::new(17, 0, 17, 16), Rule::parse("B36/S23").unwrap());
+    assert_eq!(both.rule_at(0, 0), &Rule::default());
+    assert_eq!(both.rule_at(5, 20).to_string(), "B36/S23");
+
+    for _ in 0..4 {
+        conway.tick();
+        highlife.tick();
+        both.tick();
+    }
+    assert_ne!(conway.cells, highlife.cells);
+    assert_eq!(
+        both.sub_universe(Rect::new(0, 0, 16, 16)).cells,
+        conway.cells
+    );
+    assert_eq!(
+        both.sub_universe(Rect::new(18, 0, 16, 16)).cells,
+        highlife.cells
+    );
+}
+
+#[test]
+fn figur_placement() {
+    let dot = Universe::from_str("O").unwrap();
+    let pos = |area: Area| {
+        let univ = Universe::from_figur(area, dot.clone()).unwrap();
+        let idx = univ.cells.iter().position(|&c| c == Cell::Alive).unwrap();
+        (idx / area.width as usize, idx % area.width as usize)
+    };
+    // even difference: exactly in the middle
+    assert_eq!(pos(Area::new(5, 3)), (1, 2));
+    // odd difference: the extra margin is at the bottom and the right
+    assert_eq!(pos(Area::new(4, 2)), (0, 1));
+    assert_eq!(pos(Area::new(6, 5)), (2, 2));
+
+    assert_eq!(universe::centering_offset(10, 3), 3);
+    assert_eq!(universe::centering_offset(9, 3), 3);
+    assert_eq!(universe::centering_offset(3, 3), 0);
+}
+
+#[test]
+fn render_box_closes() {
+    let width = |s: &str| ratatui::text::Span::raw(s).width();
+    for w in [0, 1, 2, 7] {
+        let univ = shapes::full(Area::new(w, 2));
+        for glyphs in Glyphs::ALL {
+            let rendered = univ.render(glyphs);
+            let lines = rendered.lines().collect::<Vec<_>>();
+            let top = width(lines[0]);
+            for line in &lines[1..] {
+                assert_eq!(width(line), top, "{glyphs:?}, {w} wide: {line:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn minimal_period_tile() {
+    let tile = Universe::from_str(
+        "\
+O.
+.O",
+    )
+    .unwrap();
+    let mut board = shapes::empty(Area::new(8, 6));
+    for row in (0..6).step_by(2) {
+        for col in (0..8).step_by(2) {
+            board.stamp(&tile, row, col);
+        }
+    }
+    assert_eq!(board.minimal_period_tile(), Area::new(2, 2));
+
+    // stripes only repeat one way
+    let mut stripes = shapes::empty(Area::new(6, 4));
+    stripes.stamp(&shapes::full(Area::new(6, 1)), 1, 0);
+    assert_eq!(stripes.minimal_period_tile(), Area::new(1, 4));
+
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    assert_eq!(glider.minimal_period_tile(), glider.area);
+    assert_eq!(
+        shapes::empty(Area::new(5, 3)).minimal_period_tile(),
+        Area::new(1, 1)
+    );
+
+    // checking the whole width adds past `u16::MAX`
+    let mut wide = shapes::empty(Area::new(40_000, 1));
+    wide.set_cell(0, 39_999, Cell::Alive);
+    assert_eq!(wide.minimal_period_tile(), wide.area);
+}
+
+#[test]
+fn speed_gauge() {
+    assert_eq!(ui::speed_ratio(MAX_POLL), 0.0);
+    assert_eq!(ui::speed_ratio(MIN_POLL), 1.0);
+    // out of range is clamped
+    assert_eq!(ui::speed_ratio(PAUSE), 0.0);
+    assert_eq!(ui::speed_ratio(Duration::ZERO), 1.0);
+    // geometric mean is halfway
+    let mid = Duration::from_secs_f64((MIN_POLL.as_secs_f64() * MAX_POLL.as_secs_f64()).sqrt());
+    assert!((ui::speed_ratio(mid) - 0.5).abs() < 1e-9);
+    assert!(ui::speed_ratio(DEF_DUR) > ui::speed_ratio(DEF_DUR * 2));
+
+    assert_eq!(ui::speed_bar(0.0, 4), "░░░░");
+    assert_eq!(ui::speed_bar(0.5, 4), "██░░");
+    assert_eq!(ui::speed_bar(1.0, 4), "████");
+
+    let mut app = App::default();
+    for _ in 0..100 {
+        app.faster(false);
+    }
+    assert_eq!(app.poll_t, MIN_POLL);
+    for _ in 0..100 {
+        app.slower(true);
+    }
+    assert_eq!(app.poll_t, MAX_POLL);
+}
+
+/// Everything a `Config` can hold
+fn full_config() -> Config {
+    Config {
+        area: Some(Area::new(30, 20)),
+        rule: Some(Rule::parse("B36/S23").unwrap()),
+        boundary: Some(BoundaryMode::Dead),
+        glyphs: Some(Glyphs::Emoji),
+        pattern: Some(
+            Universe::from_str(shapes::GLIDER)
+                .unwrap()
+                .with_name("my \"glider\""),
+        ),
+    }
+}
+
+#[test]
+#[cfg(feature = "config")]
+fn config_round_trip() {
+    let config = full_config();
+    let written = config.to_string();
+    let read = written.parse::<Config>().unwrap();
+    assert_eq!(read, config, "{written}");
+    assert_eq!(
+        read.pattern.as_ref().map(Universe::name),
+        Some("my \"glider\"")
+    );
+
+    let commented = "# highlife\nrule = \"B36/S23\" # replicators\n\ntopology = \"toroidal\"";
+    let config = commented.parse::<Config>().unwrap();
+    assert_eq!(config.boundary, Some(BoundaryMode::Toroidal));
+    assert_eq!(config.universe(), None);
+
+    assert!("width = 3".parse::<Config>().is_err());
+    assert!("colour = \"red\"".parse::<Config>().is_err());
+    assert!("rule = B3/S23".parse::<Config>().is_err());
+}
+
+#[test]
+fn config_setup() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = App::default().with_config(full_config());
+    let mut terminal = Terminal::new(TestBackend::new(80, 50)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    let univ = &app.universe;
+    assert_eq!(univ.area, Area::new(30, 20));
+    assert_eq!(univ.boundary(), BoundaryMode::Dead);
+    assert_eq!(univ.rule().to_string(), "B36/S23");
+    assert_eq!(univ.population(), 5);
+    assert_eq!(app.glyphs, Some(Glyphs::Emoji));
+
+    // not fitted to the terminal later either
+    app.square = true;
+    let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    assert_eq!(app.universe.area, Area::new(30, 20));
+    assert_eq!(app.universe.population(), 5);
+
+    // only a pattern: fitted as usual
+    let config = Config {
+        area: None,
+        ..full_config()
+    };
+    let mut app = App::default().with_config(config);
+    let mut terminal = Terminal::new(TestBackend::new(80, 50)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    assert_ne!(app.universe.area, Area::new(30, 20));
+    assert_eq!(app.universe.area, app.area);
+}
+
+#[test]
+fn toggle_cells() {
+    let mut univ = shapes::empty(Area::new(4, 3));
+    univ.toggle_cells(&[(0, 0), (1, 2), (2, 3)]).unwrap();
+    assert_eq!(univ.population(), 3);
+    assert_eq!(univ[(1u16, 2u16)], Cell::Alive);
+    assert_eq!(univ[(2u16, 3u16)], Cell::Alive);
+
+    // twice is back to dead
+    univ.toggle_cells(&[(1, 2), (1, 2), (0, 0)]).unwrap();
+    assert_eq!(univ[(1u16, 2u16)], Cell::Alive);
+    assert_eq!(univ[(0u16, 0u16)], Cell::Dead);
+
+    // all or nothing
+    let before = univ.clone();
+    let err = univ.toggle_cells(&[(1, 1), (3, 0)]).unwrap_err();
+    assert_eq!(err, "(3;0) is out of range: universe is 4x3");
+    assert_eq!(univ.cells, before.cells);
+}
+
+#[test]
+fn population_series() {
+    let blinker = Universe::from_str("OOO").unwrap();
+    let mut univ = Universe::from_figur(Area::new(5, 5), blinker).unwrap();
+    let series = univ.population_series(6);
+    assert_eq!(series.len(), 7);
+    assert_eq!(series, (0..=6).map(|g| (g, 3)).collect::<Vec<_>>());
+    assert_eq!(univ.generation(), 6);
+
+    // continues from where it is
+    let pair = Universe::from_str("OO").unwrap();
+    let mut univ = Universe::from_figur(Area::new(5, 5), pair).unwrap();
+    univ.tick();
+    assert_eq!(univ.population_series(2), [(1, 0), (2, 0), (3, 0)]);
+
+    let mut ticks = 0;
+    univ.run_for_with_progress(4, |u| {
+        ticks += 1;
+        assert_eq!(u.generation(), 3 + ticks);
+    });
+    assert_eq!(ticks, 4);
+}
+
+#[test]
+fn rule_change_mid_run() {
+    let mut univ = shapes::rand_seeded(Area::new(24, 16), 7);
+    for _ in 0..5 {
+        univ.tick();
+    }
+    univ.set_rule(Rule::parse("B36/S23").unwrap());
+    // the same cells, never ticked before
+    let mut fresh = Universe::new(univ.area, univ.cells.clone(), "fresh");
+    fresh.set_rule(Rule::parse("B36/S23").unwrap());
+    for _ in 0..5 {
+        univ.tick();
+        fresh.tick();
+        assert_eq!(univ.cells, fresh.cells);
+    }
+
+    univ.set_rule(Rule::parse("B2/S").unwrap());
+    fresh.set_rule(Rule::parse("B2/S").unwrap());
+    univ.tick();
+    fresh.tick();
+    assert_eq!(univ.cells, fresh.cells);
+}
+
+#[test]
+fn binary_round_trip() {
+    let mut univ = shapes::rand_seeded(Area::new(13, 7), 42).with_name("rändom");
+    univ.set_rule(Rule::parse("B36/S23").unwrap());
+    univ.set_boundary(BoundaryMode::Dead);
+    univ.tick();
+    univ.tick();
+
+    let mut saved = Vec::new();
+    univ.save_binary(&mut saved).unwrap();
+    assert!(saved.starts_with(b"CGOL\x01"));
+    // 91 cells in 12 bytes
+    assert_eq!(
+        saved.len(),
+        4 + 1 + 2 + 2 + 2 + 2 + 1 + 8 + 2 + "rändom".len() + 12
+    );
+
+    let loaded = Universe::load_binary(saved.as_slice()).unwrap();
+    assert_eq!(loaded, univ);
+    assert_eq!(loaded.rule(), univ.rule());
+    assert_eq!(loaded.boundary(), BoundaryMode::Dead);
+    assert_eq!(loaded.generation(), 2);
+}
+
+#[test]
+fn binary_errors() {
+    let mut saved = Vec::new();
+    shapes::full(Area::new(3, 3))
+        .save_binary(&mut saved)
+        .unwrap();
+
+    let mut wrong_magic = saved.clone();
+    wrong_magic[0] = b'X';
+    let err = Universe::load_binary(wrong_magic.as_slice()).unwrap_err();
+    assert!(err.starts_with("not a binary universe"), "{err}");
+
+    let mut wrong_version = saved.clone();
+    wrong_version[4] = 9;
+    let err = Universe::load_binary(wrong_version.as_slice()).unwrap_err();
+    assert!(err.contains("version 9"), "{err}");
+
+    let truncated = &saved[..saved.len() - 1];
+    assert!(Universe::load_binary(truncated).is_err());
+
+    // the biggest size there is, without the cells
+    let mut huge = saved[..saved.len() - 2].to_vec();
+    huge[5..9].fill(0xff);
+    let err = Universe::load_binary(huge.as_slice()).unwrap_err();
+    assert!(
+        err.ends_with("536854529 bytes of cells expected, only got 0"),
+        "{err}"
+    );
+}
+
+#[test]
+fn run_until_delta() {
+    // a blinker changes 4 cells every generation
+    let blinker = Universe::from_str("OOO").unwrap();
+    let mut univ = Universe::from_figur(Area::new(5, 5), blinker.clone()).unwrap();
+    assert_eq!(univ.run_until_delta(3, 100), 1);
+    assert_eq!(univ.run_until_delta(4, 10), 10);
+    assert_eq!(univ.generation(), 11);
+
+    // the blinker next to a pair dying at once
+    let mut univ = Universe::from_figur(Area::new(12, 5), blinker).unwrap();
+    univ.toggle_cells(&[(0, 0), (0, 1)]).unwrap();
+    assert_eq!(univ.run_until_delta(5, 100), 1);
+
+    // a still life stops right away
+    let mut block = Universe::from_figur(Area::new(6, 6), shapes::full(Area::new(2, 2))).unwrap();
+    assert_eq!(block.run_until_delta(0, 100), 1);
+
+    let mut app = App::new(
+        Area::new(9, 9),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.execute("skip 3").unwrap();
+    assert_eq!(app.universe.generation(), 1);
+    assert_eq!(app.message.as_deref(), Some("skipped 1 generations"));
+    assert!(app.execute("skip").is_err());
+    assert!(app.execute("skip many").is_err());
+}
+
+#[test]
+fn message_fades() {
+    let mut app = App::new(
+        Area::new(9, 9),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.execute("skip 3").unwrap();
+    for _ in 0..MESSAGE_GENS {
+        app.tick();
+    }
+    assert_eq!(app.message.as_deref(), Some("skipped 1 generations"));
+    app.tick();
+    assert_eq!(app.message, None);
+
+    // a new message is shown for as long
+    app.execute("skip 3").unwrap();
+    app.tick();
+    assert!(app.message.is_some());
+}
+
+#[test]
+fn skip_ticks_the_app() {
+    use std::{cell::Cell as Counter, rc::Rc};
+
+    // the glider changes 4 or 5 cells every generation
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let area = Area::new(8, 8);
+    let mut app = App::new(
+        area,
+        vec![Universe::from_figur(area, glider).unwrap()],
+        DEF_DUR,
+    );
+    let calls = Rc::new(Counter::new(0));
+    let counter = Rc::clone(&calls);
+    app.tick_hook = Some(Box::new(move |_| counter.set(counter.get() + 1)));
+
+    app.breakpoint = Some(20);
+    app.execute("skip 5").unwrap();
+    assert_eq!(app.universe.generation(), 20);
+    assert_eq!(
+        app.message.as_deref(),
+        Some("skipped 20 generations, reached generation 20")
+    );
+    // stopped there, like playing
+    assert!(app.paused());
+    app.play_pause();
+    assert_eq!(app.poll_t, DEF_DUR);
+    // every generation went through `App::tick`
+    assert_eq!(calls.get(), 20);
+    assert_eq!(app.history.len(), 20);
+    assert_eq!(app.cycle, None);
+    assert!(app.step_back());
+    assert_eq!(app.universe.generation(), 19);
+
+    // the glider is back where it started after 32 generations
+    app.breakpoint = None;
+    app.execute("skip 5").unwrap();
+    assert_eq!(app.universe.generation(), 19 + SKIP_MAX);
+    assert_eq!(calls.get(), 20 + SKIP_MAX);
+    assert_eq!(app.cycle, Some(32));
+}
+
+#[test]
+fn index_big_board() {
+    // more cells than fit into `u16`
+    let area = Area::new(1000, 300);
+    let mut univ = shapes::empty(area);
+    univ[(299u16, 999u16)] = Cell::Alive;
+    assert_eq!(univ.cells.last(), Some(&Cell::Alive));
+    univ[(66usize, 12usize)] = Cell::Alive;
+    assert_eq!(univ.cells[66 * 1000 + 12], Cell::Alive);
+}
+
+#[test]
+#[should_panic(expected = "index out of range")]
+fn index_truncated_col() {
+    let univ = shapes::empty(Area::new(300, 300));
+    // would be column 0 if truncated to `u16`, and a valid index
+    let _ = univ[(0usize, 65536usize)];
+}
+
+#[test]
+fn mono_has_no_colors() {
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+
+    let mut app = App::new(
+        Area::new(8, 8),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.mono = true;
+    app.watermark = Watermark::Full;
+    app.show_log = true;
+    app.universe.set_boundary(BoundaryMode::Dead);
+    app.message = Some("hi".into());
+    let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    assert!(buffer
+        .content
+        .iter()
+        .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+    // drawn as text, in ASCII
+    assert_eq!(buffer[(0, 0)].symbol(), "+");
+    assert!(buffer.content.iter().any(|cell| cell.symbol() == "█"));
+}
+
+#[test]
+fn flash_on_collision() {
+    // two gliders heading for each other
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    let mut board = shapes::empty(Area::new(20, 20));
+    board.stamp(&glider, 2, 2);
+    let backwards = Universe::from_str("OO.\nO.O\nO..").unwrap();
+    board.stamp(&backwards, 9, 9);
+    let mut app = App::new(Area::new(20, 20), vec![board], DEF_DUR);
+    app.restart();
+    // each glider changes 4 cells in a generation
+    app.event_threshold = 8;
+
+    // off by default
+    app.tick();
+    assert_eq!(app.flash_for, 0);
+
+    app.flash_on_event = true;
+    let mut flashed = None;
+    for generation in 0..40 {
+        app.tick();
+        if app.flash_for > 0 {
+            flashed = Some(generation);
+            break;
+        }
+    }
+    // they collide in the 10th generation
+    assert_eq!(flashed, Some(8));
+    for _ in 0..FLASH_GENS {
+        app.on_flash_event(false);
+    }
+    assert_eq!(app.flash_for, 0);
+
+    // dying out is an event too
+    let mut app = App::new(
+        Area::new(5, 5),
+        vec![Universe::from_str("O").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.flash_on_event = true;
+    app.tick();
+    assert_eq!(app.flash_for, FLASH_GENS);
+}
+
+#[test]
+fn diff_regions() {
+    let blinker = Universe::from_str("OOO").unwrap();
+    let mut univ = shapes::empty(Area::new(20, 10));
+    univ.stamp(&blinker, 4, 3);
+    univ.stamp(&shapes::full(Area::new(2, 2)), 7, 15);
+    let prev = univ.clone();
+    assert!(univ.diff_regions(&prev).is_empty());
+
+    univ.tick();
+    // only the blinker changes: the 2 cells at its ends and the 2 above and below its middle
+    assert_eq!(univ.diff_regions(&prev), [Rect::new(3, 3, 3, 3)]);
+
+    univ.toggle_cell(0, 19);
+    let mut regions = univ.diff_regions(&prev);
+    regions.sort_by_key(|r| (r.y, r.x));
+    assert_eq!(regions, [Rect::new(19, 0, 1, 1), Rect::new(3, 3, 3, 3)]);
+}
+
+/// FNV-1a of the area and the cells: stable across platforms and Rust versions, unlike `DefaultHasher`
+fn golden_checksum(univ: &Universe) -> u64 {
+    let bytes = [univ.width().to_le_bytes(), univ.height().to_le_bytes()];
+    let cells = univ.cells.iter().map(|&c| u8::from(bool::from(c)));
+    bytes
+        .into_iter()
+        .flatten()
+        .chain(cells)
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[test]
+fn golden_ticks() {
+    let patterns = [
+        ("glider", shapes::GLIDER),
+        ("blinker", "OOO"),
+        ("block", "OO\nOO"),
+        ("r-pentomino", ".OO\nOO.\n.O."),
+    ];
+    let rules = ["B3/S23", "B36/S23", "B2/S"];
+    let boundaries = [BoundaryMode::Toroidal, BoundaryMode::Dead];
+    const GENERATIONS: u64 = 30;
+    // (population, checksum) after `GENERATIONS` on a 12x12 board
+    let golden: [(usize, u64); 24] = [
+        (5, 0x727d404ca5713b10),  // glider, B3/S23, Toroidal
+        (4, 0xccd3642294a7e359),  // glider, B3/S23, Dead
+        (5, 0x727d404ca5713b10),  // glider, B36/S23, Toroidal
+        (4, 0xccd3642294a7e359),  // glider, B36/S23, Dead
+        (35, 0x18423e2761c557ea), // glider, B2/S, Toroidal
+        (26, 0xb50baec7b06c81a1), // glider, B2/S, Dead
+        (3, 0xe14ec04a95633506),  // blinker, B3/S23, Toroidal
+        (3, 0xe14ec04a95633506),  // blinker, B3/S23, Dead
+        (3, 0xe14ec04a95633506),  // blinker, B36/S23, Toroidal
+        (3, 0xe14ec04a95633506),  // blinker, B36/S23, Dead
+        (0, 0x3e56ef7820ee7765),  // blinker, B2/S, Toroidal
+        (0, 0x3e56ef7820ee7765),  // blinker, B2/S, Dead
+        (4, 0x74fe943e29f58301),  // block, B3/S23, Toroidal
+        (4, 0x74fe943e29f58301),  // block, B3/S23, Dead
+        (4, 0x74fe943e29f58301),  // block, B36/S23, Toroidal
+        (4, 0x74fe943e29f58301),  // block, B36/S23, Dead
+        (8, 0x3149cdb3485bfa65),  // block, B2/S, Toroidal
+        (0, 0x3e56ef7820ee7765),  // block, B2/S, Dead
+        (35, 0xf22e802bdc9bdf38), // r-pentomino, B3/S23, Toroidal
+        (5, 0x9969d3f398a1a4b2),  // r-pentomino, B3/S23, Dead
+        (0, 0x3e56ef7820ee7765),  // r-pentomino, B36/S23, Toroidal
+        (0, 0x3e56ef7820ee7765),  // r-pentomino, B36/S23, Dead
+        (0, 0x3e56ef7820ee7765),  // r-pentomino, B2/S, Toroidal
+        (0, 0x3e56ef7820ee7765),  // r-pentomino, B2/S, Dead
+    ];
+
+    let mut i = 0;
+    for (name, pattern) in patterns {
+        for rule in rules {
+            for boundary in boundaries {
+                let figur = Universe::from_str(pattern).unwrap();
+                let mut univ = Universe::from_figur(Area::new(12, 12), figur).unwrap();
+                univ.set_rule(Rule::parse(rule).unwrap());
+                univ.set_boundary(boundary);
+                univ.run_for_with_progress(GENERATIONS, |_| {});
+                let got = (univ.population(), golden_checksum(&univ));
+                assert_eq!(got, golden[i], "{name}, {rule}, {boundary:?}");
+                i += 1;
+            }
+        }
+    }
+    assert_eq!(i, golden.len());
+}
+
+#[test]
+fn ruler_marks() {
+    // glyphs: 2 characters a cell
+    assert_eq!(
+        ui::ruler_marks(35, 1, 2),
+        [(0, 0), (20, 10), (40, 20), (60, 30)]
+    );
+    // braille: 2 cells a character horizontally, 4 vertically
+    assert_eq!(
+        ui::ruler_marks(35, 2, 1),
+        [(0, 0), (5, 10), (10, 20), (15, 30)]
+    );
+    assert_eq!(
+        ui::ruler_marks(35, 4, 1),
+        [(0, 0), (2, 10), (5, 20), (7, 30)]
+    );
+    assert_eq!(ui::ruler_marks(10, 1, 1), [(0, 0)]);
+    assert!(ui::ruler_marks(0, 1, 1).is_empty());
+}
+
+#[test]
+fn rulers_drawn() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = App::new(
+        Area::new(8, 8),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.glyphs = Some(Glyphs::Blocks);
+    app.show_rulers = true;
+    let mut terminal = Terminal::new(TestBackend::new(60, 30)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let line = |y: u16| (0..60).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+    // columns 0, 10 and 20 above the first cell of them: after the row ruler and the border
+    let top = line(0);
+    assert_eq!(top.find("10"), Some(5 + 1 + 20));
+    assert_eq!(top.find("20"), Some(5 + 1 + 40));
+    // row 10 left of its line: below the column ruler and the border
+    assert!(line(1 + 1 + 10).starts_with("  10 "));
+    assert_eq!(app.universe.area, Area::new(26, 26));
+}
+
+#[test]
+fn rulers_on_tiny_terminal() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    for (width, height) in [(3, 3), (6, 4), (1, 1)] {
+        for square in [false, true] {
+            let mut app = App::new(
+                Area::new(8, 8),
+                vec![Universe::from_str(shapes::GLIDER).unwrap()],
+                DEF_DUR,
+            );
+            app.show_rulers = true;
+            app.square = square;
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+        }
+    }
+}
+
+#[test]
+fn neighbours_alive_at_least() {
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+        let mut univ = shapes::rand_seeded(Area::new(9, 7), 3);
+        univ.set_boundary(boundary);
+        for row in 0..univ.height() {
+            for col in 0..univ.width() {
+                let count = univ.live_neighbour_count(row, col);
+                for n in 0..=9 {
+                    assert_eq!(
+                        univ.neighbours_alive_at_least(row, col, n),
+                        count >= n,
+                        "({row};{col}) has {count}, at least {n}? {boundary:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn breakpoint() {
+    let mut app = App::new(
+        Area::new(9, 9),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.execute("break 5").unwrap();
+    assert_eq!(app.breakpoint, Some(5));
+
+    for _ in 0..4 {
+        app.advance();
+        assert!(!app.paused());
+    }
+    app.advance();
+    assert!(app.paused());
+    assert_eq!(app.universe.generation(), 5);
+    assert_eq!(app.message.as_deref(), Some("reached generation 5"));
+
+    // resuming goes on at the same speed
+    app.play_pause();
+    assert_eq!(app.poll_t, DEF_DUR);
+    app.advance();
+    assert!(!app.paused());
+
+    app.execute("break").unwrap();
+    assert_eq!(app.breakpoint, None);
+    assert!(app.execute("break soon").is_err());
+}
+
+#[test]
+fn load_with_rule_override() {
+    let dir = std::env::temp_dir().join(format!("cgol-load-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("replicator.rle");
+    std::fs::write(
+        &path,
+        "#N Replicator\nx = 5, y = 5, rule = B36/S23\n2b3o$bo2bo$o3bo$o2bo$3o!",
+    )
+    .unwrap();
+
+    let mut app = App::new(
+        Area::new(20, 20),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.execute(&format!("load {} as B3/S23", path.display()))
+        .unwrap();
+    assert_eq!(app.universe.name(), "Replicator");
+    assert_eq!(app.universe.rule(), &Rule::default());
+    // the override stays on restart
+    app.restart();
+    assert_eq!(app.universe.rule(), &Rule::default());
+
+    // its own rule otherwise
+    app.execute(&format!("load {}", path.display())).unwrap();
+    assert_eq!(app.universe.rule().to_string(), "B36/S23");
+
+    assert!(app.execute("load").is_err());
+    assert!(app.execute(&format!("load {} as", path.display())).is_err());
+    assert!(app
+        .execute(&format!("load {} as B9", path.display()))
+        .is_err());
+    assert!(app.execute("load /nonexistent/pattern.rle").is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn from_figur_keeps_rule() {
+    let mut figur = Universe::from_str("OOO").unwrap();
+    figur.set_rule(Rule::parse("B36/S23").unwrap());
+    let univ = Universe::from_figur(Area::new(5, 5), figur).unwrap();
+    assert_eq!(univ.rule().to_string(), "B36/S23");
+}
+
+#[test]
+fn tick_hook() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let mut app = App::new(
+        Area::new(9, 9),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&seen);
+    app.tick_hook = Some(Box::new(move |univ| {
+        log.borrow_mut().push((univ.generation(), univ.clone()));
+    }));
+    for _ in 0..3 {
+        app.tick();
+    }
+    let seen = seen.borrow();
+    assert_eq!(seen.iter().map(|(g, _)| *g).collect::<Vec<_>>(), [1, 2, 3]);
+    // the state after the tick
+    assert_eq!(seen[2].1, app.universe);
+    assert_ne!(seen[1].1, app.universe);
+}
+
+#[test]
+fn from_str_rows() {
+    let univ = Universe::from_str_rows(Area::new(8, 8), &["..O", "O.O", ".OO"]).unwrap();
+    assert_eq!(univ.population(), 5);
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    assert_eq!(
+        univ.cells,
+        Universe::from_figur(Area::new(8, 8), glider).unwrap().cells
+    );
+
+    assert!(Universe::from_str_rows(Area::new(2, 2), &["OOO"]).is_err());
+    assert!(Universe::from_str_rows(Area::new(8, 8), &["OxO"]).is_err());
+    assert!(Universe::from_str_rows(Area::new(8, 8), &[]).is_err());
+}
+
+#[test]
+fn pause_on_menu() {
+    let mut app = App::new(
+        Area::new(9, 9),
+        vec![Universe::from_str("OOO").unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    assert!(app.pause_on_menu);
+
+    app.command = Some("tog".into());
+    app.on_timeout();
+    assert_eq!(app.universe.generation(), 0);
+
+    app.pause_on_menu = false;
+    app.on_timeout();
+    app.command = None;
+    app.show_log = true;
+    app.on_timeout();
+    assert_eq!(app.universe.generation(), 2);
+
+    app.pause_on_menu = true;
+    app.show_log = false;
+    app.on_timeout();
+    assert_eq!(app.universe.generation(), 3);
+}
+
+#[test]
+fn copperhead_rle() {
+    let rle = "\
+#N Copperhead
+#C c/10 orthogonal spaceship
+x = 12, y = 8, rule = B3/S23
+5bob2o$4bo6bo$3b2o3bo2bo$2obo5b2o$2obo5b2o$3b2o3bo2bo$4bo6bo$5bob2o!";
+    let copperhead = Universe::from_str(shapes::COPPERHEAD).unwrap();
+    let parsed = Universe::from_rle(rle).unwrap();
+    assert_eq!(parsed, copperhead);
+    assert_eq!(Universe::from_rle(&parsed.to_rle()).unwrap(), copperhead);
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn load_gzipped() {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let plain = Universe::load(&fixtures.join("gosper.rle")).unwrap();
+    assert_eq!(
+        plain,
+        Universe::from_str(shapes::GOSPER_GLIDER_GUN).unwrap()
+    );
+    // dynamic Huffman codes, with a file name in the header
+    assert_eq!(
+        Universe::load(&fixtures.join("gosper.rle.gz")).unwrap(),
+        plain
+    );
+    // stored blocks
+    assert_eq!(
+        Universe::load(&fixtures.join("gosper-stored.rle.gz")).unwrap(),
+        plain
+    );
+
+    let mut corrupt = std::fs::read(fixtures.join("gosper.rle.gz")).unwrap();
+    // in the checksum
+    let crc = corrupt.len() - 5;
+    corrupt[crc] ^= 1;
+    assert!(gzip::decompress(&corrupt)
+        .unwrap_err()
+        .contains("checksum mismatch"));
+    assert!(gzip::decompress(&corrupt[..40]).is_err());
+    assert_eq!(checksum::crc32(b"123456789"), 0xcbf4_3926);
+
+    // no bigger than allowed
+    let gz = std::fs::read(fixtures.join("gosper.rle.gz")).unwrap();
+    let len = gzip::decompress(&gz).unwrap().len();
+    assert!(gzip::decompress_at_most(&gz, len).is_ok());
+    let err = gzip::decompress_at_most(&gz, len - 1).unwrap_err();
+    assert!(
+        err.ends_with(&format!("more than {} bytes of output", len - 1)),
+        "{err}"
+    );
+    let stored = std::fs::read(fixtures.join("gosper-stored.rle.gz")).unwrap();
+    assert!(gzip::decompress_at_most(&stored, len - 1).is_err());
+}
+
+#[test]
+#[cfg(not(feature = "gzip"))]
+fn load_gzipped_needs_feature() {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let err = Universe::load(&fixtures.join("gosper.rle.gz")).unwrap_err();
+    assert!(
+        err.ends_with("gzipped, reading it needs the gzip feature"),
+        "{err}"
+    );
+}
+
+#[test]
+fn gosper_rle_round_trip() {
+    let gun = Universe::from_str(shapes::GOSPER_GLIDER_GUN).unwrap();
+    let rle = gun.to_rle();
+    assert!(rle.starts_with("#N Gosper glider gun\nx = 36, y = 9, rule = B3/S23\n"));
+    assert_eq!(Universe::from_rle(&rle).unwrap(), gun);
+
+    // blank rows in between collapse, trailing ones are dropped
+    let mut sparse = shapes::empty(Area::new(5, 9)).with_name("");
+    sparse.toggle_cells(&[(0, 4), (5, 0)]).unwrap();
+    assert_eq!(sparse.to_rle(), "x = 5, y = 9, rule = B3/S23\n4bo5$o!\n");
+}
+
+#[test]
+fn count_transitions_to_stable() {
+    let count = |pattern: &str, max| {
+        let figur = Universe::from_str(pattern).unwrap();
+        Universe::from_figur(Area::new(8, 8), figur)
+            .unwrap()
+            .count_transitions_to_stable(max)
+    };
+    // the ends die, then the middle
+    assert_eq!(count("O..\n.O.\n..O", 100), 3);
+    // one birth makes a block
+    assert_eq!(count("OO\nO.", 100), 1);
+    assert_eq!(count("OO\nOO", 100), 0);
+    // a blinker never settles
+    assert_eq!(count("OOO", 10), 40);
+}
+
+#[test]
+fn responsive_keys_hint() {
+    for width in 0..100 {
+        let hint = ui::keys_hint(width);
+        assert!(hint.len() <= width, "{hint:?} doesn't fit into {width}");
+    }
+    assert_eq!(
+        ui::keys_hint(200),
+        "[q]uit, [r]estart, pause: [ ], nav: vim/arrows, [:]command"
+    );
+    assert_eq!(ui::keys_hint(40), "[q]uit, [r]estart, [ ]pause, [:]cmd");
+    assert_eq!(ui::keys_hint(14), "[q]uit, [:]cmd");
+    assert_eq!(ui::keys_hint(13), "");
+}
+
+#[test]
+fn day_and_night_symmetry() {
+    // live and dead cells swap roles under Day & Night
+    let rule = Rule::parse("B3678/S34678").unwrap();
+    let mut univ = shapes::rand_seeded(Area::new(16, 12), 11);
+    univ.set_rule(rule.clone());
+    let cells = univ.cells.iter().map(|&cell| !cell).collect();
+    let mut inverse = Universe::new(univ.area, cells, "inverse");
+    inverse.set_rule(rule);
+    for _ in 0..10 {
+        univ.tick();
+        inverse.tick();
+        assert!(univ
+            .cells
+            .iter()
+            .zip(&inverse.cells)
+            .all(|(&a, &b)| a == !b));
+    }
+}
+
+#[test]
+fn rotate_cw_fit() {
+    // 5 wide, but the grid is only 3 high
+    let univ = Universe::from_str_rows(Area::new(7, 3), &["OO.OO", "O...."]).unwrap();
+    let rotated = univ.rotate_cw_fit();
+    assert_eq!(rotated.area, Area::new(7, 5));
+    assert_eq!(rotated.population(), univ.population());
+    assert_eq!(
+        rotated.crop_to_content(),
+        Universe::from_str("OO\n.O\n..\n.O\n.O").unwrap()
+    );
+    // four turns and it's back to how it was
+    let back = (0..3).fold(rotated, |univ, _| univ.rotate_cw_fit());
+    assert_eq!(back.crop_to_content(), univ.crop_to_content());
+
+    let blinker = Universe::from_str_rows(Area::new(5, 5), &["OOO"]).unwrap();
+    let rotated = blinker.rotate_cw_fit();
+    assert_eq!(rotated.area, blinker.area);
+    assert_eq!(rotated.cells, {
+        let mut univ = blinker.clone();
+        univ.tick();
+        univ.cells
+    });
+}
+
+#[test]
+fn corner_blinker() {
+    let mut toroidal = shapes::empty(Area::new(5, 5));
+    toroidal.toggle_cells(&[(0, 0), (0, 1), (0, 2)]).unwrap();
+    let mut dead = toroidal.clone();
+    dead.set_boundary(BoundaryMode::Dead);
+
+    toroidal.tick();
+    dead.tick();
+    // wrapping over the top edge
+    assert_eq!(toroidal.population(), 3);
+    assert_eq!(toroidal[(4u16, 1u16)], Cell::Alive);
+    // the top cell falls off
+    assert_eq!(dead.population(), 2);
+    assert_eq!(dead[(4u16, 1u16)], Cell::Dead);
+
+    toroidal.tick();
+    dead.tick();
+    assert_eq!(toroidal.population(), 3);
+    assert_eq!(dead.population(), 0);
+}
+
+#[test]
+fn from_coords() {
+    let coords = parse_coords("0,1 1,2  2,0 2,1 2,2").unwrap();
+    assert_eq!(coords, [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    let univ = Universe::from_coords(Area::new(3, 3), &coords).unwrap();
+    assert_eq!(
+        univ.cells,
+        Universe::from_str(".O.\n..O\nOOO").unwrap().cells
+    );
+
+    assert!(Universe::from_coords(Area::new(2, 3), &coords).is_err());
+    assert!(parse_coords("0,1 1").is_err());
+    assert!(parse_coords("0,-1").is_err());
+    assert_eq!(parse_coords("").unwrap(), []);
+}
+
+#[test]
+fn generation_counter() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    for mut univ in [
+        shapes::rand(Area::new(8, 8)),
+        shapes::stripes(Area::new(8, 8)),
+    ] {
+        assert_eq!(univ.generation(), 0);
+        univ.tick();
+        univ.toggle_cell(1, 1);
+        assert_eq!(univ.generation(), 1);
+        let centered = Universe::from_figur(Area::new(10, 10), univ).unwrap();
+        assert_eq!(centered.generation(), 0);
+    }
+
+    let mut app = App::new(
+        Area::new(8, 8),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    app.tick();
+    app.tick();
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let footer = (0..80)
+        .map(|x| buffer[(x, 29)].symbol())
+        .collect::<String>();
+    assert!(footer.contains("gen: 2, pop: 5"), "{footer:?}");
+}
+
+#[test]
+fn is_stable() {
+    let block = Universe::from_str_rows(Area::new(6, 6), &["OO", "OO"]).unwrap();
+    assert!(block.is_stable());
+    assert_eq!(block.generation(), 0);
+
+    let blinker = Universe::from_str_rows(Area::new(5, 5), &["OOO"]).unwrap();
+    let before = blinker.clone();
+    assert!(!blinker.is_stable());
+    assert_eq!(blinker.cells, before.cells);
+
+    let mut block = block;
+    block.set_rule(Rule::parse("B3/S").unwrap());
+    assert!(!block.is_stable());
+}
+
+#[test]
+fn block_population() {
+    let mut block = Universe::from_str_rows(Area::new(6, 6), &["OO", "OO"]).unwrap();
+    for _ in 0..5 {
+        assert_eq!(block.population(), 4);
+        block.tick();
+    }
+}
+
+#[test]
+fn crop_view() {
+    let area = Area::new(40, 30);
+    let whole = Rect::new(0, 0, 40, 30);
+    assert_eq!(ui::crop_view(None, None, area, 4), whole);
+    // margin around the live cells, clipped to the universe
+    let bbox = Rect::new(10, 8, 3, 3);
+    assert_eq!(
+        ui::crop_view(Some(bbox), None, area, 4),
+        Rect::new(6, 4, 11, 11)
+    );
+    assert_eq!(
+        ui::crop_view(Some(Rect::new(1, 27, 3, 3)), None, area, 4),
+        Rect::new(0, 23, 8, 7)
+    );
+    // a view from another universe isn't kept
+    let too_big = Rect::new(30, 20, 20, 20);
+    assert_eq!(ui::crop_view(None, Some(too_big), area, 4), whole);
+
+    // follow a glider without moving at every generation
+    let mut univ = shapes::empty(area);
+    univ.stamp(&Universe::from_str(shapes::GLIDER).unwrap(), 2, 2);
+    let mut view = None;
+    let mut moves = 0;
+    for _ in 0..60 {
+        let bbox = univ.bounding_box().unwrap();
+        let next = ui::crop_view(Some(bbox), view, area, 4);
+        assert_eq!(next.intersection(bbox), bbox);
+        if view.is_some_and(|view| view != next) {
+            moves += 1;
+        }
+        view = Some(next);
+        univ.tick();
+    }
+    assert!((1..15).contains(&moves), "moved {moves} times");
+
+    assert_eq!(ui::zoom(Area::new(11, 11), Area::new(80, 60)), 5);
+    assert_eq!(ui::zoom(Area::new(100, 11), Area::new(80, 60)), 1);
+}
+
+#[test]
+fn crop_view_drawn() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = App::new(
+        Area::new(8, 8),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.glyphs = Some(Glyphs::Blocks);
+    app.auto_crop_view = true;
+    let mut terminal = Terminal::new(TestBackend::new(60, 30)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+
+    // glider with 4 cells of margin, each cell drawn 2 times as big: 2 by 2 glyphs
+    assert_eq!(
+        app.crop_view.map(|view| (view.width, view.height)),
+        Some((11, 11))
+    );
+    let buffer = terminal.backend().buffer();
+    let alive = (0..60)
+        .flat_map(|x| (0..29).map(move |y| (x, y)))
+        .filter(|&pos| buffer[pos].symbol() == "█")
+        .count();
+    assert_eq!(alive, 5 * 2 * 2);
+}
+
+#[test]
+fn detect_period() {
+    let blinker = Universe::from_str_rows(Area::new(5, 5), &["OOO"]).unwrap();
+    assert_eq!(blinker.detect_period(10), Some(2));
+    assert_eq!(blinker.detect_period(1), None);
+    let block = Universe::from_str_rows(Area::new(6, 6), &["OO", "OO"]).unwrap();
+    assert_eq!(block.detect_period(10), Some(1));
+    // comes back to the same place only after crossing the whole torus
+    let glider = Universe::from_str_rows(Area::new(8, 8), &["..O", "O.O", ".OO"]).unwrap();
+    assert_eq!(glider.detect_period(100), Some(32));
+    assert_eq!(glider.generation(), 0);
+}
+
+#[test]
+fn counts() {
+    for univ in [
+        shapes::rand_seeded(Area::new(13, 7), 5),
+        shapes::empty(Area::new(4, 4)),
+        Universe::from_str(shapes::GLIDER).unwrap(),
+    ] {
+        let (alive, dead) = univ.counts();
+        assert_eq!(
+            alive + dead,
+            usize::from(univ.width()) * usize::from(univ.height())
+        );
+        assert_eq!(alive, univ.population());
+    }
+}
+
+#[test]
+fn board_map() {
+    let glyphs = ui::BoardMap {
+        inner: Rect::new(1, 1, 20, 10),
+        per_char: Area::new(1, 1),
+        chars_per_cell: Area::new(2, 1),
+        view: Rect::new(0, 0, 10, 10),
+        zoom: 1,
+    };
+    // the border
+    assert_eq!(glyphs.cell_at(0, 0), None);
+    assert_eq!(glyphs.cell_at(1, 1), Some((0, 0)));
+    // both characters of a cell
+    assert_eq!(glyphs.cell_at(5, 3), Some((2, 2)));
+    assert_eq!(glyphs.cell_at(6, 3), Some((2, 2)));
+    assert_eq!(glyphs.cell_at(20, 10), Some((9, 9)));
+    assert_eq!(glyphs.cell_at(21, 10), None);
+
+    let braille = ui::BoardMap {
+        per_char: Area::new(2, 4),
+        chars_per_cell: Area::new(1, 1),
+        view: Rect::new(0, 0, 40, 40),
+        ..glyphs
+    };
+    assert_eq!(braille.cell_at(3, 2), Some((4, 4)));
+
+    let zoomed = ui::BoardMap {
+        view: Rect::new(7, 3, 5, 5),
+        zoom: 2,
+        ..glyphs
+    };
+    assert_eq!(zoomed.cell_at(1, 1), Some((3, 7)));
+    assert_eq!(zoomed.cell_at(5, 3), Some((4, 8)));
+    // beyond the view
+    assert_eq!(zoomed.cell_at(1, 11), None);
+}
+
+#[test]
+fn mouse_toggle() {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = App::new(
+        Area::new(8, 8),
+        vec![shapes::empty(Area::new(8, 8))],
+        DEF_DUR,
+    );
+    app.glyphs = Some(Glyphs::Blocks);
+    let mut terminal = Terminal::new(TestBackend::new(30, 20)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+
+    let mouse = |kind, column, row| MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    app.mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+    assert_eq!(app.universe[(2u16, 2u16)], Cell::Alive);
+    // still the same cell
+    app.mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 6, 3));
+    app.mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 7, 3));
+    app.mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 9, 3));
+    assert_eq!(app.universe.population(), 3);
+    assert_eq!(app.universe[(2u16, 3u16)], Cell::Alive);
+    assert_eq!(app.universe[(2u16, 4u16)], Cell::Alive);
+    // clicking again toggles it back
+    app.mouse(mouse(MouseEventKind::Down(MouseButton::Left), 5, 3));
+    assert_eq!(app.universe[(2u16, 2u16)], Cell::Dead);
+    // only the left button, only on the board
+    app.mouse(mouse(MouseEventKind::Down(MouseButton::Right), 11, 3));
+    app.mouse(mouse(MouseEventKind::Down(MouseButton::Left), 0, 0));
+    assert_eq!(app.universe.population(), 2);
+}
+
+#[test]
+fn roll() {
+    let mut univ = Universe::from_str_rows(Area::new(5, 4), &["O", ".O"]).unwrap();
+    let original = univ.clone();
+    univ.roll(-2, 3);
+    assert_eq!(univ.population(), 2);
+    assert_eq!(univ[(0u16, 0u16)], Cell::Alive);
+    assert_eq!(univ[(3u16, 4u16)], Cell::Alive);
+    univ.roll(2, -3);
+    assert_eq!(univ, original);
+    univ.roll(8, 15);
+    assert_eq!(univ, original);
+
+    // the torus has no special place
+    let mut rolled_first = shapes::rand_seeded(Area::new(17, 11), 8);
+    let mut ticked_first = rolled_first.clone();
+    for (d_row, d_col) in [(3, -5), (-20, 40), (0, 1)] {
+        rolled_first.roll(d_row, d_col);
+        rolled_first.tick();
+        ticked_first.tick();
+        ticked_first.roll(d_row, d_col);
+        assert_eq!(rolled_first, ticked_first);
+    }
+}
+
+#[test]
+fn save_and_load() {
+    let path = std::env::temp_dir().join(format!("cgol-tui-save-{}.cells", std::process::id()));
+    let area = Area::new(9, 7);
+    let univ = Universe::from_figur(area, Universe::from_str(shapes::GLIDER).unwrap()).unwrap();
+    let mut app = App::new(area, vec![univ], DEF_DUR);
+    app.restart();
+    app.save_path = path.clone();
+    app.universe.toggle_cell(0, 0);
+    app.save().unwrap();
+    let saved = app.universe.clone();
+
+    app.tick();
+    app.universe.set_boundary(BoundaryMode::Dead);
+    app.load().unwrap();
+    assert_eq!(app.universe, saved);
+    assert_eq!(app.universe.boundary(), BoundaryMode::Dead);
+    // the dead margins are kept
+    assert_eq!(Universe::load_from_path(&path).unwrap().area, area);
+
+    std::fs::write(&path, "!Name: ragged\n.O.\nO\n").unwrap();
+    let err = Universe::load_from_path(&path).unwrap_err();
+    assert!(err.contains("row 2"), "{err}");
+    // still fine for the lenient loader
+    assert!(Universe::load(&path).is_ok());
+    std::fs::write(&path, "!Name: nothing\n").unwrap();
+    assert!(Universe::load_from_path(&path).is_err());
+    // the board isn't touched
+    assert!(app.load().is_err());
+    assert_eq!(app.universe, saved);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cells_snapshot() {
+    let mut univ = shapes::rand_seeded(Area::new(12, 9), 4);
+    let snapshot = univ.cells_snapshot();
+    let before = univ.clone();
+    for _ in 0..3 {
+        univ.tick();
+    }
+    assert_ne!(univ, before);
+    univ.restore_cells(snapshot).unwrap();
+    assert_eq!(univ, before);
+
+    assert!(univ.restore_cells(vec![Cell::Alive; 12 * 9 - 1]).is_err());
+    assert_eq!(univ, before);
+}
+
+#[test]
+fn step_back_or_replay() {
+    let area = Area::new(16, 12);
+    let mut app = App::default().with_seed(42);
+    app.area = area;
+    app.restart();
+    let mut states = vec![app.universe.clone()];
+    for _ in 0..HISTORY_LEN + 40 {
+        app.tick();
+        states.push(app.universe.clone());
+    }
+    let gen = |app: &App| app.universe.generation();
+
+    // still in the history
+    app.step_back_or_replay(90).unwrap();
+    assert_eq!(gen(&app), 90);
+    assert_eq!(app.universe, states[90]);
+    // run again from the seed
+    app.step_back_or_replay(3).unwrap();
+    assert_eq!(gen(&app), 3);
+    assert_eq!(app.universe, states[3]);
+    assert!(app.step_back_or_replay(3).is_err());
+    assert!(app.step_back_or_replay(50).is_err());
+
+    // changed by hand: replaying wouldn't give the same board
+    for _ in 0..HISTORY_LEN + 20 {
+        app.tick();
+    }
+    let before = app.history.back().unwrap().clone();
+    app.universe.toggle_cell(0, 0);
+    let edited = app.universe.clone();
+    let err = app.step_back_or_replay(1).unwrap_err();
+    assert!(err.contains("can't go back"), "{err}");
+    assert_eq!(app.universe, edited);
+    // the history is still exact
+    app.step_back_or_replay(gen(&app) - 1).unwrap();
+    assert_eq!(app.universe, before);
+
+    // not random, history exhausted
+    let mut app = App::new(
+        area,
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    for _ in 0..HISTORY_LEN + 6 {
+        app.tick();
+    }
+    assert!(app.step_back_or_replay(10).is_ok());
+    assert!(app.step_back_or_replay(2).is_err());
+    assert_eq!(gen(&app), 10);
+}
+
+#[test]
+fn undo_redo() {
+    let mut app = App::new(
+        Area::new(10, 10),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    assert!(!app.undo());
+    let start = app.universe.clone();
+    app.tick();
+    let ticked = app.universe.clone();
+    app.execute("toggle 0,0 9,9").unwrap();
+    let edited = app.universe.clone();
+
+    assert!(app.undo());
+    assert_eq!(app.universe, ticked);
+    assert!(app.undo());
+    assert_eq!(app.universe, start);
+    assert!(!app.undo());
+    assert!(app.redo());
+    assert_eq!(app.universe, ticked);
+    assert!(app.redo());
+    assert_eq!(app.universe, edited);
+    assert_eq!(app.universe.generation(), 1);
+    assert!(!app.redo());
+
+    // a new edit forgets what was undone
+    assert!(app.undo());
+    app.execute("fill 5,5 6,6").unwrap();
+    assert!(!app.redo());
+    assert!(app.undo());
+    assert_eq!(app.universe, ticked);
+    // invalid edits aren't remembered
+    assert!(app.execute("toggle 10,10").is_err());
+    assert!(app.undo());
+    assert_eq!(app.universe, start);
+}
+
+#[test]
+fn step_back() {
+    let mut app = App::new(
+        Area::new(10, 10),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    assert!(!app.step_back());
+    let start = app.universe.clone();
+    app.tick();
+    let ticked = app.universe.clone();
+    app.tick();
+    app.execute("toggle 0,0").unwrap();
+    app.execute("toggle 1,1").unwrap();
+
+    // the edits go too
+    assert!(app.step_back());
+    assert_eq!(app.universe, ticked);
+    assert_eq!(app.universe.generation(), 1);
+    assert!(app.step_back());
+    assert_eq!(app.universe, start);
+    assert!(!app.step_back());
+    assert_eq!(app.universe, start);
+
+    // only so far back
+    for _ in 0..HISTORY_LEN + 10 {
+        app.tick();
+    }
+    for _ in 0..HISTORY_LEN {
+        assert!(app.step_back());
+    }
+    assert!(!app.step_back());
+    assert_eq!(app.universe.generation(), 10);
+
+    // past clearing, like `x` does
+    app.tick();
+    let before_clear = app.universe.clone();
+    app.remember();
+    app.universe.clear();
+    app.execute("toggle 2,2").unwrap();
+    assert_eq!(app.universe.generation(), 0);
+    assert!(app.step_back());
+    assert_eq!(app.universe, before_clear);
+    assert_eq!(app.universe.generation(), 11);
+    assert!(app.step_back());
+    assert_eq!(app.universe.generation(), 10);
+}
+
+#[test]
+fn step_while_paused() {
+    let mut app = App::new(
+        Area::new(10, 10),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    let mut expected = app.universe.clone();
+    app.step();
+    assert_eq!(app.universe.generation(), 0);
+
+    app.play_pause();
+    for gen in 1..=3 {
+        app.step();
+        expected.tick();
+        assert_eq!(app.universe.generation(), gen);
+        assert_eq!(app.universe, expected);
+    }
+    assert!(app.paused());
+}
+
+#[test]
+fn universe_resize() {
+    let mut univ = Universe::from_str_rows(Area::new(8, 8), &["OO", "OO"]).unwrap();
+    univ.resize(Area::new(16, 16));
+    assert_eq!(univ.area, Area::new(16, 16));
+    assert_eq!(univ.population(), 4);
+    assert_eq!(univ.bounding_box(), Some(Rect::new(7, 7, 2, 2)));
+
+    // the corners fall off
+    let mut univ =
+        Universe::from_str_rows(Area::new(6, 6), &["O....O", "..OO..", "..OO..", "O....O"])
+            .unwrap();
+    univ.resize(Area::new(4, 4));
+    assert_eq!(univ.population(), 4);
+    assert_eq!(univ.bounding_box(), Some(Rect::new(1, 1, 2, 2)));
+}
+
+#[test]
+fn clear() {
+    let mut univ = shapes::rand_seeded(Area::new(12, 7), 3);
+    univ.tick();
+    univ.clear();
+    assert_eq!(univ.population(), 0);
+    assert_eq!(univ.area, Area::new(12, 7));
+    assert_eq!(univ.cells.len(), 12 * 7);
+    assert_eq!(univ.generation(), 0);
+}
+
+#[test]
+fn fill_alive() {
+    let mut univ = shapes::rand_seeded(Area::new(12, 7), 3);
+    univ.tick();
+    univ.fill_alive();
+    assert_eq!(univ.population(), 12 * 7);
+    assert_eq!(univ.generation(), 0);
+    // on a torus every cell has 8 neighbours: all die of overcrowding
+    univ.set_rule(Rule::parse("B3/S23").unwrap());
+    univ.tick();
+    assert_eq!(univ.population(), 0);
+}
+
+#[test]
+fn invert() {
+    let mut univ = shapes::rand_seeded(Area::new(12, 7), 3);
+    univ.tick();
+    let orig = univ.clone();
+    univ.invert();
+    assert_eq!(univ.population(), 12 * 7 - orig.population());
+    assert_eq!(univ.generation(), 1);
+    univ.invert();
+    assert_eq!(univ, orig);
+    assert_eq!(univ.generation(), 1);
+}
+
+#[test]
+fn cell_access() {
+    let univ = Universe::from_str(shapes::GLIDER).unwrap();
+    assert_eq!(univ.cells().len(), 9);
+    assert_eq!(
+        univ.cells().iter().filter(|&&c| c == Cell::Alive).count(),
+        univ.population()
+    );
+    for row in 0..3 {
+        for col in 0..3 {
+            assert_eq!(
+                univ.cell_at(row, col),
+                univ.cells()[usize::from(row * 3 + col)]
+            );
+        }
+    }
+    assert_eq!(univ.cell_at(0, 2), Cell::Alive);
+    assert_eq!(univ.cell_at(3, 0), Cell::Dead);
+    assert_eq!(univ.cell_at(0, 3), Cell::Dead);
+    assert_eq!(univ.cell_at(u16::MAX, u16::MAX), Cell::Dead);
+}
+
+/// Size and pixels of an 8-bit grayscale PNG written by `Universe::to_png`
+#[cfg(all(feature = "image", feature = "gzip"))]
+fn read_png(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    let be = |b: &[u8]| u32::from_be_bytes(b[..4].try_into().unwrap());
+    assert_eq!(
+        png[..8],
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+    );
+    let (mut pos, mut size, mut idat) = (8, (0, 0), Vec::new());
+    while pos < png.len() {
+        let len = be(&png[pos..]) as usize;
+        let data = &png[pos + 8..pos + 8 + len];
+        assert_eq!(
+            be(&png[pos + 8 + len..]),
+            checksum::crc32(&png[pos + 4..pos + 8 + len])
+        );
+        match &png[pos + 4..pos + 8] {
+            b"IHDR" => {
+                size = (be(data), be(&data[4..]));
+                assert_eq!(data[8..], [8, 0, 0, 0, 0]);
+            }
+            b"IDAT" => idat.extend_from_slice(data),
+            _ => {}
+        }
+        pos += 12 + len;
+    }
+    let (width, height) = size;
+    let (raw, read) = gzip::inflate(&idat[2..], usize::MAX).unwrap();
+    assert_eq!(idat[2 + read..], png::adler32(&raw).to_be_bytes());
+    let pixels = raw
+        .chunks(width as usize + 1)
+        .flat_map(|row| {
+            // no filter
+            assert_eq!(row[0], 0);
+            row[1..].to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(pixels.len(), (width * height) as usize);
+    (width, height, pixels)
+}
+
+#[test]
+#[cfg(all(feature = "image", feature = "gzip"))]
+fn to_png() {
+    let univ = Universe::from_str(shapes::GLIDER).unwrap();
+    let path = std::env::temp_dir().join(format!("cgol-tui-{}.png", std::process::id()));
+    univ.to_png(&path, 3).unwrap();
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let (width, height, pixels) = read_png(&png);
+    assert_eq!((width, height), (9, 9));
+    for (i, &px) in pixels.iter().enumerate() {
+        let (row, col) = (i as u16 / 9 / 3, i as u16 % 9 / 3);
+        let expected = if univ.cell_at(row, col) == Cell::Alive {
+            0
+        } else {
+            0xff
+        };
+        assert_eq!(px, expected, "pixel {i}");
+    }
+
+    assert!(univ.to_png(&path, 0).is_err());
+    assert!(!path.exists());
+    assert!(univ.to_png(&path, u32::MAX).is_err());
+    assert_eq!(png::adler32(b"Wikipedia"), 0x11e6_0398);
+}
+
+#[test]
+#[cfg(all(feature = "image", feature = "gzip"))]
+fn to_png_big() {
+    let univ = shapes::rand_seeded(Area::new(200, 100), 5);
+    let path = std::env::temp_dir().join(format!("cgol-tui-big-{}.png", std::process::id()));
+    univ.to_png(&path, 4).unwrap();
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let (width, height, pixels) = read_png(&png);
+    assert_eq!((width, height), (800, 400));
+    let black = pixels.iter().filter(|&&px| px == 0).count();
+    assert_eq!(black, univ.population() * 16);
+    // runs of a cell and repeated rows are back-references
+    assert!(png.len() < pixels.len() / 10, "{} bytes", png.len());
+
+    // rows too long to be referenced
+    let mut univ = shapes::rand_seeded(Area::new(33000, 2), 5);
+    univ.set_cell(1, 0, Cell::Alive);
+    univ.to_png(&path, 1).unwrap();
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let (width, height, pixels) = read_png(&png);
+    assert_eq!((width, height), (33000, 2));
+    for (i, &px) in pixels.iter().enumerate() {
+        let alive = univ.cell_at((i / 33000) as u16, (i % 33000) as u16) == Cell::Alive;
+        assert_eq!(px == 0, alive, "pixel {i}");
+    }
+}
+
+#[test]
+fn png_command() {
+    let mut app = App::new(
+        Area::new(10, 10),
+        vec![Universe::from_str(shapes::GLIDER).unwrap()],
+        DEF_DUR,
+    );
+    app.restart();
+    let path = std::env::temp_dir().join(format!("cgol-tui-cmd-{}.png", std::process::id()));
+    let cmd = format!("png {} 2", path.display());
+    #[cfg(not(feature = "image"))]
+    assert_eq!(
+        app.execute(&cmd).unwrap_err(),
+        "png needs the image feature"
+    );
+    #[cfg(feature = "image")]
+    {
+        app.execute(&cmd).unwrap();
+        assert_eq!(
+            app.message.as_deref(),
+            Some(format!("wrote {}", path.display()).as_str())
+        );
+        let png = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        // 20x20 pixels
+        assert_eq!(png[16..24], [0, 0, 0, 20, 0, 0, 0, 20]);
+
+        assert!(app.execute("png").is_err());
+        assert!(app.execute(&format!("png {} 0", path.display())).is_err());
+        assert!(app.execute(&format!("png {} x", path.display())).is_err());
+        assert!(app.execute(&format!("png {} 2 3", path.display())).is_err());
+        assert!(!path.exists());
+    }
+}
+
+#[test]
+fn from_cells_format() {
+    let input = "\
+!Name: Glider
+!a comment
+......
+..O
+...O..
+.OOO
+
+";
+    let univ = Universe::from_cells_format(input).unwrap();
+    assert_eq!(univ.area, Area::new(3, 3));
+    assert_eq!(univ.name(), "Glider");
+    assert_eq!(
+        univ,
+        Universe::from_str(".O.\n..O\nOOO")
+            .unwrap()
+            .with_name("Glider")
+    );
+
+    assert!(Universe::from_cells_format("!only a comment").is_err());
+    assert!(Universe::from_cells_format("...\n...").is_err());
+    assert!(Universe::from_cells_format(".O.\n_O_").is_err());
+}
+
+#[test]
+fn set_fps() {
+    let area = Area::new(4, 4);
+    let mut app = App::new(area, vec![shapes::empty(area)], DEF_DUR);
+
+    app.set_fps(25.0).unwrap();
+    assert_eq!(app.poll_t, Duration::from_millis(40));
+    app.execute("fps 50").unwrap();
+    assert_eq!(app.poll_t, Duration::from_millis(20));
+
+    // clamped
+    app.set_fps(1000.0).unwrap();
+    assert_eq!(app.poll_t, MIN_POLL);
+    app.set_fps(0.01).unwrap();
+    assert_eq!(app.poll_t, MAX_POLL);
+    app.set_fps(f64::INFINITY).unwrap();
+    assert_eq!(app.poll_t, MIN_POLL);
+
+    for fps in [0.0, -1.0, f64::NAN] {
+        assert!(app.set_fps(fps).is_err());
+    }
+    assert!(app.execute("fps").is_err());
+    assert!(app.execute("fps -3").is_err());
+    assert_eq!(app.poll_t, MIN_POLL);
+}
+
+#[test]
+fn speed_stays_in_range() {
+    let mut app = App::default();
+    for _ in 0..100 {
+        app.faster(true);
+        assert!(app.poll_t >= MIN_POLL);
+    }
+    assert_eq!(app.effective_poll_t(), MIN_POLL);
+    for _ in 0..100 {
+        app.slower(false);
+        assert!(app.poll_t <= MAX_POLL);
+    }
+    // and back from the ceiling
+    app.faster(false);
+    assert!(app.poll_t < MAX_POLL);
+}
+
+#[test]
+fn thin_universes() {
+    let mut bounded = Universe::from_str(".OOO.").unwrap();
+    bounded.set_boundary(BoundaryMode::Dead);
+    assert_eq!(bounded.live_neighbour_count(0, 2), 2);
+    assert_eq!(bounded.live_neighbour_count(0, 0), 1);
+    bounded.tick();
+    assert_eq!(bounded.cells, Universe::from_str("..O..").unwrap().cells);
+    assert_eq!(bounded.tick(), TickOutcome::Extinct);
+
+    // above and below are the cell itself, left and right count thrice
+    let torus = Universe::from_str(".OOO.").unwrap();
+    assert_eq!(torus.live_neighbour_count(0, 2), 2 + 3 + 3);
+    assert_eq!(torus.live_neighbour_count(0, 0), 3);
+    let torus = Universe::from_str("O").unwrap();
+    assert_eq!(torus.live_neighbour_count(0, 0), 8);
+
+    for area in [
+        Area::new(1, 5),
+        Area::new(5, 1),
+        Area::new(1, 1),
+        Area::new(2, 1),
+    ] {
+        for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+            let mut univ = shapes::full(area);
+            univ.set_boundary(boundary);
+            for _ in 0..4 {
+                univ.tick();
+            }
+        }
+    }
+}
+
+#[test]
+fn oscillator_eq() {
+    let blinker = Universe::from_str(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    let mut ticked = blinker.clone();
+    ticked.tick();
+    assert_ne!(ticked, blinker);
+    ticked.tick();
+    // the generation doesn't matter
+    assert_eq!(ticked.generation(), 2);
+    assert_eq!(ticked, blinker);
+
+    // neither do the rule, the boundary and the name
+    ticked.set_rule(Rule::parse("B36/S23").unwrap());
+    ticked.set_boundary(BoundaryMode::Dead);
+    assert_eq!(ticked.with_name("other"), blinker);
+    // but the size does
+    let mut bigger = blinker.clone();
+    bigger.resize(Area::new(7, 5));
+    assert_ne!(bigger, blinker);
+}
+
+#[test]
+fn builder() {
+    let univ = Universe::builder().build();
+    assert_eq!(univ.area, Area::new(32, 32));
+    assert_eq!(univ.population(), 0);
+    assert_eq!(univ.rule(), &Rule::default());
+    assert_eq!(univ.boundary(), BoundaryMode::Toroidal);
+    assert_eq!(univ.generation(), 0);
+
+    let rule = Rule::parse("B36/S23").unwrap();
+    let univ = Universe::builder()
+        .width(5)
+        .height(3)
+        .name("full")
+        .rule(rule.clone())
+        .boundary(BoundaryMode::Dead)
+        .fill(Cell::Alive)
+        .build();
+    assert_eq!(univ, shapes::full(Area::new(5, 3)));
+    assert_eq!(univ.rule(), &rule);
+    assert_eq!(univ.boundary(), BoundaryMode::Dead);
+}
+
+#[test]
+fn cycle_detection() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let blinker = Universe::from_str(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    let mut app = App::new(blinker.area, vec![blinker.clone()], DEF_DUR);
+    assert_eq!(app.universe.state_hash(), blinker.state_hash());
+    app.tick();
+    assert_ne!(app.universe.state_hash(), blinker.state_hash());
+    assert_eq!(app.cycle, None);
+    app.tick();
+    assert_eq!(app.cycle, Some(2));
+    app.tick();
+    assert_eq!(app.cycle, Some(2));
+    app.restart();
+    assert_eq!(app.cycle, None);
+
+    // a glider comes back after 4 generations for each cell of the torus
+    let glider = Universe::from_str(shapes::GLIDER).unwrap();
+    for (size, period) in [(6, Some(24)), (16, Some(64)), (17, None)] {
+        let area = Area::new(size, size);
+        let univ = Universe::from_figur(area, glider.clone()).unwrap();
+        let mut app = App::new(area, vec![univ], DEF_DUR);
+        for _ in 0..4 * u64::from(size) {
+            app.tick();
+        }
+        // longer than `CYCLE_WINDOW`: missed
+        assert_eq!(app.cycle, period, "{size}x{size}");
+    }
+
+    // shown in the footer, once the board fits the terminal
+    let mut app = App::new(blinker.area, vec![blinker], DEF_DUR);
+    let mut terminal = Terminal::new(TestBackend::new(160, 20)).unwrap();
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    for _ in 0..2 {
+        app.tick();
+    }
+    terminal.draw(|f| ui::ui(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let footer = (0..160)
+        .map(|x| buffer[(x, 19)].symbol())
+        .collect::<String>();
+    assert!(footer.contains("cycle detected (period ≈ 2)"), "{footer}");
+}
+
+/// The next generation's cells, counting each cell's neighbours one by one
+fn reference_tick(univ: &Universe) -> Vec<Cell> {
+    (0..univ.height())
+        .flat_map(|row| (0..univ.width()).map(move |col| (row, col)))
+        .map(|(row, col)| {
+            let live = univ.live_neighbour_count(row, col);
+            univ.rule_at(row, col).next(univ[(row, col)], live)
+        })
+        .collect()
+}
+
+#[test]
+fn neighbour_cache() {
+    let mut univ = shapes::rand_seeded(Area::new(23, 17), 7);
+    univ.add_region_rule(Rect::new(3, 2, 8, 6), Rule::parse("B36/S23").unwrap());
+    for boundary in [
+        BoundaryMode::Toroidal,
+        BoundaryMode::Dead,
+        BoundaryMode::Toroidal,
+    ] {
+        univ.set_boundary(boundary);
+        for _ in 0..10 {
+            let expected = reference_tick(&univ);
+            univ.tick();
+            assert_eq!(univ.cells, expected, "{boundary:?}");
+        }
+    }
+    // clones don't copy the indices, they're computed again on their first tick
+    assert!(univ.has_neighbour_cache());
+    let mut clone = univ.clone();
+    assert!(!clone.has_neighbour_cache());
+    clone.tick();
+    assert!(clone.has_neighbour_cache());
+    let mut app = App::new(univ.area, vec![univ.clone()], DEF_DUR);
+    for _ in 0..3 {
+        app.tick();
+    }
+    assert!(app.universe.has_neighbour_cache());
+    assert!(app.history.iter().all(|univ| !univ.has_neighbour_cache()));
+
+    // rebuilt for the new size
+    for area in [Area::new(30, 9), Area::new(5, 5), Area::new(1, 4)] {
+        univ.resize(area);
+        let expected = reference_tick(&univ);
+        univ.tick();
+        assert_eq!(univ.cells, expected, "{area:?}");
+    }
+}
+
+#[test]
+fn packed_universe() {
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+        let mut univ = shapes::rand_seeded(Area::new(37, 21), 11);
+        univ.set_rule(Rule::parse("B36/S23").unwrap());
+        univ.set_boundary(boundary);
+        let mut packed = PackedUniverse::try_from(&univ).unwrap();
+        assert_eq!(packed.to_universe(), univ);
+        for _ in 0..50 {
+            assert_eq!(packed.tick(), univ.tick());
+            assert_eq!(packed.population(), univ.population());
+        }
+        assert_eq!(packed.to_universe(), univ);
+        assert_eq!(packed.generation(), 50);
+        assert_eq!(format!("{packed}"), format!("{univ}"));
+        assert_eq!(format!("{packed:#}"), format!("{univ:#}"));
+    }
+
+    // thin ones too
+    let mut univ = Universe::from_str("OO.O.OOO").unwrap();
+    let mut packed = PackedUniverse::try_from(&univ).unwrap();
+    for _ in 0..5 {
+        assert_eq!(packed.tick(), univ.tick());
+        assert_eq!(packed.to_universe(), univ);
+    }
+
+    let mut packed = PackedUniverse::try_from(&shapes::empty(Area::new(70, 2))).unwrap();
+    packed.set_cell(1, 69, Cell::Alive);
+    packed.toggle_cell(0, 64);
+    packed.toggle_cell(0, 0);
+    packed.toggle_cell(0, 0);
+    assert_eq!(packed.cell_at(1, 69), Cell::Alive);
+    assert_eq!(packed.cell_at(0, 64), Cell::Alive);
+    assert_eq!(packed.cell_at(0, 0), Cell::Dead);
+    assert_eq!(packed.population(), 2);
+    packed.set_cell(1, 69, Cell::Dead);
+    assert_eq!(packed.population(), 1);
+
+    // equal as `Universe`s are: the generation, the rule and the name don't matter
+    let blinker = Universe::from_str(".....\n.....\n.OOO.\n.....\n.....").unwrap();
+    let mut ticked = blinker.clone().with_name("other");
+    ticked.set_rule(Rule::parse("B36/S23").unwrap());
+    ticked.tick();
+    let packed = PackedUniverse::try_from(&blinker).unwrap();
+    let mut other = PackedUniverse::try_from(&ticked).unwrap();
+    assert_ne!(packed, other);
+    other.tick();
+    assert_eq!(packed, other);
+
+    // region rules would be lost
+    let mut univ = shapes::rand_seeded(Area::new(10, 10), 1);
+    univ.add_region_rule(Rect::new(0, 0, 5, 5), Rule::parse("B36/S23").unwrap());
+    assert!(PackedUniverse::try_from(&univ).is_err());
+    univ.clear_region_rules();
+    assert!(PackedUniverse::try_from(&univ).is_ok());
+}
+
+#[test]
+fn packed_tick_edges() {
+    // words end inside, at and after the rows; tori so small cells are their own neighbours
+    let sizes = [
+        (1, 1),
+        (2, 3),
+        (5, 1),
+        (63, 4),
+        (64, 5),
+        (65, 3),
+        (128, 2),
+        (130, 7),
+    ];
+    for (seed, (width, height)) in sizes.into_iter().enumerate() {
+        for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+            for rule in ["B3/S23", "B36/S23", "B0/S8", "B1/S012345678"] {
+                let mut univ = shapes::rand_seeded(Area::new(width, height), seed as u64);
+                univ.set_rule(Rule::parse(rule).unwrap());
+                univ.set_boundary(boundary);
+                let mut packed = PackedUniverse::try_from(&univ).unwrap();
+                for i in 0..8 {
+                    assert_eq!(
+                        packed.tick(),
+                        univ.tick(),
+                        "{width}x{height} {boundary:?} {rule} tick {i}"
+                    );
+                    assert_eq!(
+                        packed.to_universe(),
+                        univ,
+                        "{width}x{height} {boundary:?} {rule} tick {i}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn tick_reuses_buffers() {
+    let mut univ = shapes::rand_seeded(Area::new(40, 30), 2);
+    let mut reference = univ.clone();
+    // the first tick makes the spare buffer
+    let first = univ.cells.as_ptr();
+    univ.tick();
+    let ptrs = [first, univ.cells.as_ptr()];
+    reference.cells = reference_tick(&reference);
+    for i in 0..20 {
+        let expected = reference_tick(&reference);
+        reference.cells.clone_from(&expected);
+        univ.tick();
+        assert_eq!(univ.cells, expected);
+        // no new buffers: the two are swapped
+        assert_eq!(univ.cells.as_ptr(), ptrs[i % 2]);
+    }
+}
+
+#[test]
+fn tick_in_threads() {
+    for boundary in [BoundaryMode::Toroidal, BoundaryMode::Dead] {
+        let mut serial = shapes::rand_seeded(Area::new(61, 43), 9);
+        serial.set_boundary(boundary);
+        serial.add_region_rule(Rect::new(10, 5, 20, 20), Rule::parse("B36/S23").unwrap());
+        let mut parallel = [2, 3, 8, 100].map(|threads| (threads, serial.clone()));
+        for _ in 0..30 {
+            let outcome = serial.tick_in_threads(1);
+            for (threads, univ) in &mut parallel {
+                assert_eq!(univ.tick_in_threads(*threads), outcome);
+                assert_eq!(univ.cells, serial.cells, "{threads} threads, {boundary:?}");
+            }
+        }
+    }
+    // the default picks the number of threads
+    let mut univ = shapes::rand_seeded(Area::new(200, 100), 4);
+    let mut serial = univ.clone();
+    univ.tick();
+    serial.tick_in_threads(1);
+    assert_eq!(univ.cells, serial.cells);
+}
@@ -0,0 +1,54 @@
+use crate::app::Universe;
+use ratatui::{style::Color, widgets::canvas::Shape};
+
+/// Max number of points kept
+const MAX_LEN: usize = 512;
+/// Max distance the center of mass may jump in a generation and still be the same component
+const MAX_JUMP: f64 = 3.;
+
+/// Trail of the center of mass of one component, eg.: a spaceship, as (row;col)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Trace {
+    pub path: Vec<(f64, f64)>,
+}
+
+impl Trace {
+    /// Start tracing the component of `univ` closest to (`row`;`col`), `None` if there's none
+    pub fn select(univ: &Universe, row: f64, col: f64) -> Option<Self> {
+        let start = Self::closest(univ, (row, col), f64::INFINITY)?;
+        Some(Trace { path: vec![start] })
+    }
+
+    /// Append the center of mass of the component closest to the last one.
+    /// Returns `false` if it's lost: no component is close enough.
+    pub fn follow(&mut self, univ: &Universe) -> bool {
+        let Some(&last) = self.path.last() else {
+            return false;
+        };
+        let Some(next) = Self::closest(univ, last, MAX_JUMP) else {
+            return false;
+        };
+        if self.path.len() == MAX_LEN {
+            self.path.remove(0);
+        }
+        self.path.push(next);
+        true
+    }
+
+    fn closest(univ: &Universe, to: (f64, f64), max_dist: f64) -> Option<(f64, f64)> {
+        let dist = |(row, col): (f64, f64)| (row - to.0).hypot(col - to.1);
+        univ.components()
+            .iter()
+            .filter_map(|component| Universe::center_of_mass(component))
+            .filter(|&com| dist(com) <= max_dist)
+            .min_by(|&a, &b| dist(a).total_cmp(&dist(b)))
+    }
+}
+
+impl Shape for Trace {
+    fn draw(&self, painter: &mut ratatui::widgets::canvas::Painter) {
+        for &(row, col) in &self.path {
+            painter.paint(col.round() as usize, row.round() as usize, Color::Yellow);
+        }
+    }
+}
@@ -1,9 +1,11 @@
-use crate::{app::App, app::Area};
+use super::{MAX_POLL, MIN_POLL};
+use crate::{app::App, app::Area, app::BoundaryMode, app::Glyphs, app::Universe};
 use ratatui::{
-    layout::{Constraint, Layout},
-    style::Stylize,
-    text::Line,
-    widgets::{canvas::Canvas, Block, BorderType},
+    layout::{Alignment, Constraint, Layout, Margin, Position, Rect},
+    style::{Color, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{canvas::Canvas, Block, BorderType, Clear, Paragraph},
     Frame,
 };
 
@@ -12,6 +14,198 @@ const BRAILLE: Area = Area {
     width: 2,
     height: 4,
 };
+/// characters taken by a cell drawn with `Glyphs`
+const GLYPH: Area = Area {
+    width: 2,
+    height: 1,
+};
+
+/// What to burn onto the bottom-right corner of the canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Watermark {
+    #[default]
+    Off,
+    Generation,
+    /// generation and population
+    Full,
+}
+impl Watermark {
+    pub fn next(self) -> Self {
+        match self {
+            Watermark::Off => Watermark::Generation,
+            Watermark::Generation => Watermark::Full,
+            Watermark::Full => Watermark::Off,
+        }
+    }
+}
+
+/// Text of the watermark, population is only shown if there is some
+pub fn watermark(generation: u64, population: Option<usize>) -> String {
+    match population {
+        Some(pop) => format!(" gen: {generation}, pop: {pop} "),
+        None => format!(" gen: {generation} "),
+    }
+}
+
+/// border for `App::mono`
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// characters left of the board for the row ruler
+const RULER_WIDTH: u16 = 5;
+/// cells between the labels of the rulers
+const RULER_STEP: u16 = 10;
+
+/// (offset in characters, cell index) of the labels of a ruler along `cells` cells,
+/// `per_char` cells being drawn in one character, or one cell in `chars_per_cell` characters
+pub fn ruler_marks(cells: u16, per_char: u16, chars_per_cell: u16) -> Vec<(u16, u16)> {
+    (0..cells)
+        .step_by(RULER_STEP.into())
+        .map(|cell| (cell * chars_per_cell / per_char, cell))
+        .collect()
+}
+
+/// Key hints for the footer, from the most to the least detailed
+const KEYS_HINTS: [&str; 3] = [
+    "[q]uit, [r]estart, pause: [ ], nav: vim/arrows, [:]command",
+    "[q]uit, [r]estart, [ ]pause, [:]cmd",
+    "[q]uit, [:]cmd",
+];
+
+/// The most detailed key hints fitting into `width` columns, empty if none fit
+pub fn keys_hint(width: usize) -> &'static str {
+    KEYS_HINTS
+        .into_iter()
+        .find(|hint| Span::raw(*hint).width() <= width)
+        .unwrap_or_default()
+}
+
+/// characters in the speed bar
+const SPEED_BAR_LEN: usize = 10;
+
+/// How fast `poll_t` is between `MAX_POLL`: `0.0` and `MIN_POLL`: `1.0`.
+/// Logarithmic, as `App::faster` and `App::slower` change it by a factor.
+pub fn speed_ratio(poll_t: std::time::Duration) -> f64 {
+    let poll_t = poll_t.clamp(MIN_POLL, MAX_POLL).as_secs_f64();
+    let (min, max) = (MIN_POLL.as_secs_f64(), MAX_POLL.as_secs_f64());
+    (max / poll_t).ln() / (max / min).ln()
+}
+
+/// `ratio` of `len` characters filled
+pub fn speed_bar(ratio: f64, len: usize) -> String {
+    let filled = (ratio.clamp(0.0, 1.0) * len as f64).round() as usize;
+    "█".repeat(filled) + &"░".repeat(len - filled)
+}
+
+/// Area of the universe on a canvas fitting `fits` cells, either all of it or a square
+pub fn fit_area(fits: Area, square: bool) -> Area {
+    if square {
+        let side = fits.width.min(fits.height);
+        Area::new(side, side)
+    } else {
+        fits
+    }
+}
+
+/// The part of `universe` shown on a canvas of `canvas` cells, if it doesn't fit completely
+pub fn clipped(universe: Area, canvas: Area) -> Option<Area> {
+    (!canvas.contains(universe)).then(|| {
+        Area::new(
+            universe.width.min(canvas.width),
+            universe.height.min(canvas.height),
+        )
+    })
+}
+
+/// cells shown around the live ones with `App::auto_crop_view`
+const CROP_MARGIN: u16 = 4;
+
+/// `rect` grown by `by` cells on each side, clipped to `bounds`
+fn expand(rect: Rect, by: u16, bounds: Rect) -> Rect {
+    let left = rect.x.saturating_sub(by).max(bounds.x);
+    let top = rect.y.saturating_sub(by).max(bounds.y);
+    let right = rect.right().saturating_add(by).min(bounds.right());
+    let bottom = rect.bottom().saturating_add(by).min(bounds.bottom());
+    Rect::new(left, top, right - left, bottom - top)
+}
+
+/// The region of `universe` to show with `App::auto_crop_view`: the `bbox` of the live cells
+/// and `margin` cells around it. `prev` is kept while the live cells are well inside it,
+/// and it's not much bigger than needed, so that the view doesn't jump at every generation.
+pub fn crop_view(bbox: Option<Rect>, prev: Option<Rect>, universe: Area, margin: u16) -> Rect {
+    let whole = Rect::new(0, 0, universe.width, universe.height);
+    let prev = prev.filter(|&prev| whole.intersection(prev) == prev);
+    let Some(bbox) = bbox else {
+        return prev.unwrap_or(whole);
+    };
+    let wanted = expand(bbox, margin, whole);
+    // the live cells with half the margin should still be inside
+    let needed = expand(bbox, margin / 2, whole);
+    match prev {
+        Some(prev)
+            if prev.intersection(needed) == needed
+                && prev.width <= wanted.width * 2
+                && prev.height <= wanted.height * 2 =>
+        {
+            prev
+        }
+        _ => wanted,
+    }
+}
+
+/// How many times `view` can be magnified to still fit into `room`, at least once
+pub fn zoom(view: Area, room: Area) -> u16 {
+    (room.width / view.width.max(1))
+        .min(room.height / view.height.max(1))
+        .max(1)
+}
+
+/// Every cell of `univ` as a `factor` by `factor` square
+fn zoomed(univ: &Universe, factor: u16) -> Universe {
+    let area = Area::new(univ.width() * factor, univ.height() * factor);
+    let cells = (0..area.height)
+        .flat_map(|row| (0..area.width).map(move |col| univ[(row / factor, col / factor)]))
+        .collect();
+    Universe::new(area, cells, univ.name())
+}
+
+/// Where the universe was drawn in the last frame, to find the cell under the mouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardMap {
+    /// inside the border
+    pub inner: Rect,
+    /// cells in one character
+    pub per_char: Area,
+    /// characters one cell takes
+    pub chars_per_cell: Area,
+    /// the region of the universe drawn, `x` being the column, `y` the row
+    pub view: Rect,
+    /// how many times the cells are magnified
+    pub zoom: u16,
+}
+impl BoardMap {
+    /// The (`row`;`col`) of the cell drawn at the `column` and `row` of the terminal, if any.
+    /// With braille dots, a character holds several cells, its top-left one is picked.
+    pub fn cell_at(&self, column: u16, row: u16) -> Option<(u16, u16)> {
+        if !self.inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        let col =
+            (column - self.inner.x) / self.chars_per_cell.width * self.per_char.width / self.zoom;
+        let row =
+            (row - self.inner.y) / self.chars_per_cell.height * self.per_char.height / self.zoom;
+        (col < self.view.width && row < self.view.height)
+            .then_some((self.view.y + row, self.view.x + col))
+    }
+}
 
 /// ```text
 ///  _cgol_______________
@@ -27,40 +221,276 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     let cgol = Block::bordered()
         .border_type(BorderType::Rounded)
         .title(format!(" Conway's Game of Life - {} ", app.universe.name));
+    let cgol = if app.mono {
+        cgol.border_set(ASCII_BORDER)
+    } else {
+        cgol
+    };
+    let cgol = if app.flash_for > 0 {
+        cgol.border_style(Color::LightRed)
+    } else {
+        cgol
+    };
+    let glyphs = if app.mono {
+        Some(Glyphs::Mono)
+    } else {
+        app.glyphs
+    };
+    let cgol = if app.universe.boundary() == BoundaryMode::Dead {
+        cgol.title_bottom(" bounded ".dark_gray())
+    } else {
+        cgol
+    };
+    // cells in one character
+    let per_char = if glyphs.is_some() {
+        Area::new(1, 1)
+    } else {
+        BRAILLE
+    };
+    let chars_per_cell = if glyphs.is_some() {
+        GLYPH
+    } else {
+        Area::new(1, 1)
+    };
+    // leave room for the rulers above and left of the board
+    let canvas = if app.show_rulers {
+        Rect::new(
+            chunks[0].x + RULER_WIDTH,
+            chunks[0].y + 1,
+            chunks[0].width.saturating_sub(RULER_WIDTH),
+            chunks[0].height.saturating_sub(1),
+        )
+    } else {
+        chunks[0]
+    };
     // 2 blocks less: border
-    let new_area = Area::new(
-        (chunks[0].width - 2) * BRAILLE.width,
-        (chunks[0].height - 2) * BRAILLE.height,
+    let new_area = fit_area(
+        Area::new(
+            canvas.width.saturating_sub(2) / chars_per_cell.width * per_char.width,
+            canvas.height.saturating_sub(2) / chars_per_cell.height * per_char.height,
+        ),
+        app.square,
     );
-    // apply the area change
+    // apply the area change, unless it's set by a config
+    let new_area = app.fixed_area.unwrap_or(new_area);
     if app.area != new_area {
-        app.area = new_area;
-        app.restart();
+        app.resize(new_area);
+    }
+    // shrink the board around a square universe, keeping it centered
+    let board = if app.square && app.fixed_area.is_none() {
+        let width = new_area.width / per_char.width * chars_per_cell.width + 2;
+        let height = new_area.height / per_char.height * chars_per_cell.height + 2;
+        Rect::new(
+            canvas.x + canvas.width.saturating_sub(width) / 2,
+            canvas.y + canvas.height.saturating_sub(height) / 2,
+            width,
+            height,
+        )
+    } else {
+        canvas
+    };
+    // the region of the universe drawn, and how many times it's magnified
+    let (clipped, shown, view, factor) = if app.auto_crop_view {
+        let view = crop_view(
+            app.universe.bounding_box(),
+            app.crop_view,
+            app.universe.area,
+            CROP_MARGIN,
+        );
+        app.crop_view = Some(view);
+        let view_area = Area::new(view.width, view.height);
+        let factor = zoom(view_area, new_area);
+        let shown = zoomed(&app.universe.sub_universe(view), factor);
+        (Some(view_area), Some(shown), view, factor)
+    } else {
+        let clipped = clipped(app.universe.area, new_area);
+        let visible = clipped.unwrap_or(app.universe.area);
+        let view = Rect::new(0, 0, visible.width, visible.height);
+        // only draw what fits, the rest would wrap around
+        let shown = clipped.map(|_| app.universe.sub_universe(view));
+        (clipped, shown, view, 1)
+    };
+    app.board = Some(BoardMap {
+        inner: board.inner(Margin::new(1, 1)),
+        per_char,
+        chars_per_cell,
+        view,
+        zoom: factor,
+    });
+    let shown = shown.as_ref().unwrap_or(&app.universe);
+    if let Some(glyphs) = glyphs {
+        let lines = shown
+            .cells
+            .chunks(shown.width().max(1).into())
+            .map(|row| {
+                Line::from(
+                    row.iter()
+                        .map(|&cell| glyphs.cell(cell))
+                        .collect::<String>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        f.render_widget(Paragraph::new(lines).block(cgol), board);
+    } else {
+        let universe = Canvas::default()
+            // .x_bounds([0., chunks[0].height as f64 * 2. - 4.])
+            // .y_bounds([0., chunks[0].height as f64 * 2. - 4.])
+            .paint(|ctx| {
+                ctx.draw(shown);
+                // its path is in the coordinates of the whole universe
+                if let Some(trace) = app.trace.as_ref().filter(|_| !app.auto_crop_view) {
+                    ctx.draw(trace);
+                }
+            })
+            .block(cgol);
+        f.render_widget(universe, board);
+    }
+
+    if app.show_rulers {
+        let marks = |cells: u16, per_char: u16, chars_per_cell: u16, len: u16| {
+            ruler_marks(cells, per_char, chars_per_cell)
+                .into_iter()
+                .filter(move |&(offset, _)| offset < len)
+        };
+        // inside the border
+        let inner = board.inner(Margin::new(1, 1));
+        let mut top = String::new();
+        for (offset, label) in marks(
+            shown.width(),
+            per_char.width,
+            chars_per_cell.width,
+            inner.width,
+        ) {
+            // don't overwrite the previous label
+            if top.len() <= offset.into() {
+                top += &" ".repeat(usize::from(offset) - top.len());
+                top += &label.to_string();
+            }
+        }
+        let top_ruler = Rect::new(inner.x, board.y.saturating_sub(1), inner.width, 1);
+        f.render_widget(
+            Line::from(top.dark_gray()),
+            top_ruler.intersection(f.area()),
+        );
+        for (offset, label) in marks(
+            shown.height(),
+            per_char.height,
+            chars_per_cell.height,
+            inner.height,
+        ) {
+            let left_ruler = Rect::new(
+                board.x.saturating_sub(RULER_WIDTH),
+                inner.y + offset,
+                RULER_WIDTH,
+                1,
+            );
+            let text = Line::from(format!("{label} ").dark_gray()).alignment(Alignment::Right);
+            f.render_widget(text, left_ruler);
+        }
+    }
+
+    if app.watermark != Watermark::Off {
+        let pop = (app.watermark == Watermark::Full).then(|| app.universe.population());
+        let text = Line::from(watermark(app.universe.generation(), pop).dark_gray())
+            .alignment(Alignment::Right);
+        // inside the border, in the last line
+        let corner = Rect::new(
+            board.x + 1,
+            board.bottom().saturating_sub(2),
+            board.width.saturating_sub(2),
+            1,
+        );
+        f.render_widget(text, corner);
     }
-    let universe = Canvas::default()
-        // .x_bounds([0., chunks[0].height as f64 * 2. - 4.])
-        // .y_bounds([0., chunks[0].height as f64 * 2. - 4.])
-        .paint(|ctx| ctx.draw(&app.universe))
-        .block(cgol);
 
-    f.render_widget(universe, chunks[0]);
+    if app.show_log {
+        let lines = app.log_entries().map(Line::raw).collect::<Vec<_>>();
+        let width = lines.iter().map(Line::width).max().unwrap_or(0).max(5) as u16 + 2;
+        let height = lines.len() as u16 + 2;
+        // bottom-left corner, inside the border
+        let popup = Rect::new(
+            board.x + 1,
+            board.bottom().saturating_sub(height + 1),
+            width,
+            height,
+        )
+        .intersection(board);
+        let block = Block::bordered().title(" log ");
+        let block = if app.mono {
+            block.border_set(ASCII_BORDER)
+        } else {
+            block
+        };
+        let log = Paragraph::new(lines).block(block);
+        f.render_widget(Clear, popup);
+        f.render_widget(log, popup);
+    }
 
     let footer = Layout::horizontal([Constraint::Fill(1)]).split(chunks[1]);
 
-    let current_keys_hint = "[q]uit, [r]estart, pause: [ ], nav: vim/arrows".yellow();
-
     let poll_t = {
         if let super::PAUSE = app.poll_t {
             "paused".into()
         } else {
-            format!("Poll time: {:.0?}", app.poll_t)
+            let poll_t = app.effective_poll_t();
+            let bar = speed_bar(speed_ratio(poll_t), SPEED_BAR_LEN);
+            let fps = 1.0 / poll_t.as_secs_f64();
+            if app.slowed_down() {
+                format!("speed: {bar} {poll_t:.0?}, {fps:.1} fps (event, slowed down)")
+            } else {
+                format!("speed: {bar} {poll_t:.0?}, {fps:.1} fps")
+            }
         }
     }
     .light_blue();
 
     let div = " | ".white();
-    let current_stats = vec![current_keys_hint, div, poll_t];
-    let footer_data = Line::from(current_stats);
+    let footer_data = if let Some(cmd) = &app.command {
+        Line::from(format!(":{cmd}"))
+    } else if let Some(msg) = &app.message {
+        Line::from(msg.clone().italic())
+    } else {
+        let gen = format!(
+            "gen: {}, pop: {}",
+            app.universe.generation(),
+            app.universe.population()
+        )
+        .light_blue();
+        let mut stats = vec![poll_t, div.clone(), gen];
+        if let Some(period) = app.cycle {
+            stats.extend([
+                div.clone(),
+                format!("cycle detected (period ≈ {period})").light_magenta(),
+            ]);
+        }
+        if let Some(seed) = app.seed() {
+            stats.extend([div.clone(), format!("seed: {seed} [c]opy").light_green()]);
+        }
+        if let Some(visible) = clipped {
+            let univ = app.universe.area;
+            stats.extend([
+                div.clone(),
+                format!(
+                    "showing {}x{} of {}x{}",
+                    visible.width, visible.height, univ.width, univ.height
+                )
+                .light_red(),
+            ]);
+        }
+        // the hints get whatever room is left
+        let used = stats.iter().map(Span::width).sum::<usize>() + div.width();
+        let hint = keys_hint(usize::from(footer[0].width).saturating_sub(used));
+        if !hint.is_empty() {
+            stats.splice(0..0, [hint.yellow(), div]);
+        }
+        Line::from(stats)
+    };
 
     f.render_widget(footer_data, footer[0]);
+
+    if app.mono {
+        for cell in &mut f.buffer_mut().content {
+            cell.set_fg(Color::Reset).set_bg(Color::Reset);
+        }
+    }
 }
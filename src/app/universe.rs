@@ -1,14 +1,198 @@
+#[cfg(feature = "gzip")]
+use super::gzip;
+#[cfg(feature = "image")]
+use super::png;
 use super::shapes;
-use crate::{app::Area, app::Cell};
-use ratatui::{style::Color, widgets::canvas::Shape};
+use crate::{app::Area, app::Cell, app::Glyphs, app::RegionRule, app::Rule};
+pub use packed::PackedUniverse;
+use ratatui::{layout::Rect, style::Color, widgets::canvas::Shape};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, Read},
+    path::Path,
+};
+
+/// 1 bit a cell storage
+mod packed;
+
+/// Summary of a headless run, see `Universe::measure_longevity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Longevity {
+    /// generations until the pattern stabilized, or `max` if it didn't
+    pub generations: u64,
+    /// period of the stabilized pattern: `Some(1)` for still lifes, `None` if it didn't stabilize
+    pub period: Option<u64>,
+    /// the most cells alive at once
+    pub peak_population: usize,
+    /// cells alive at the end of the run
+    pub final_population: usize,
+}
+
+/// A live cell would be stamped onto an already live one, see `Universe::stamp_checked`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    pub row: u16,
+    pub col: u16,
+}
+impl std::fmt::Display for Overlap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "overlapping live cell at ({};{})", self.row, self.col)
+    }
+}
+
+/// What happened during a `Universe::tick`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickOutcome {
+    /// some cells changed
+    Changed,
+    /// nothing changed: a still life
+    Stable,
+    /// no cells are alive
+    Extinct,
+}
+
+/// What's beyond the edges of a `Universe`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BoundaryMode {
+    /// edges wrap around: a torus
+    #[default]
+    Toroidal,
+    /// everything outside is dead: cells at the edges have fewer neighbours
+    Dead,
+}
+
+/// Where to start `inner` to center it in `outer`, rounding down: towards the top-left
+pub fn centering_offset(outer: u16, inner: u16) -> u16 {
+    (outer - inner) / 2
+}
+
+/// Start of `Universe::save_binary` files
+pub const BINARY_MAGIC: &[u8; 4] = b"CGOL";
+/// Version of the `Universe::save_binary` format
+pub const BINARY_VERSION: u8 = 1;
+
+/// Fewest cells for `Universe::tick` to use more threads with the `parallel` feature
+const PARALLEL_MIN_CELLS: usize = 128 * 128;
+
+/// Marks a missing neighbour in a `NeighbourCache`: outside of a `BoundaryMode::Dead` universe
+const NO_NEIGHBOUR: usize = usize::MAX;
+
+/// Indices of the 8 `Universe::neighbours` of each cell, `NO_NEIGHBOUR` where there's none.
+/// Computed for a size and a boundary, rebuilt by `update` when they change.
+/// Clones start empty: it's 64 bytes a cell, and `App::history` is full of clones.
+#[derive(Default)]
+struct NeighbourCache {
+    key: Option<(Area, BoundaryMode)>,
+    indices: Vec<[usize; 8]>,
+}
+impl Clone for NeighbourCache {
+    fn clone(&self) -> Self {
+        NeighbourCache::default()
+    }
+}
+impl std::fmt::Debug for NeighbourCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NeighbourCache")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+impl NeighbourCache {
+    /// Rebuild the indices if they were computed for another size or boundary
+    fn update(&mut self, area: Area, boundary: BoundaryMode) {
+        let key = Some((area, boundary));
+        if self.key == key {
+            return;
+        }
+        let width = usize::from(area.width);
+        self.indices = (0..area.height)
+            .flat_map(|row| (0..area.width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let mut indices = [NO_NEIGHBOUR; 8];
+                for (i, (n_row, n_col)) in neighbours(area, boundary, row, col).enumerate() {
+                    indices[i] = usize::from(n_row) * width + usize::from(n_col);
+                }
+                indices
+            })
+            .collect();
+        self.key = key;
+    }
+
+    /// The next state of the cell at `idx` by `rule`, `alive` telling which cells live now
+    fn next_state(&self, idx: usize, rule: &Rule, alive: impl Fn(usize) -> bool) -> Cell {
+        let live_neighbours = self.indices[idx]
+            .iter()
+            .filter(|&&n| n != NO_NEIGHBOUR && alive(n))
+            .count() as u8;
+        rule.next(alive(idx).into(), live_neighbours)
+    }
+}
+
+/// The coordinates of the 8 neighbours of (`row`;`col`) in `area`, wrapping around the edges,
+/// except in `BoundaryMode::Dead`, where the ones outside are left out.
+/// On a torus just 1 cell high or wide, a cell may be its own neighbour.
+fn neighbours(
+    area: Area,
+    boundary: BoundaryMode,
+    row: u16,
+    col: u16,
+) -> impl Iterator<Item = (u16, u16)> {
+    let (height, width) = (i32::from(area.height), i32::from(area.width));
+    [-1, 0, 1]
+        .into_iter()
+        .flat_map(|d_row| [-1, 0, 1].map(|d_col| (d_row, d_col)))
+        .filter(|&delta| delta != (0, 0))
+        .map(move |(d_row, d_col)| (i32::from(row) + d_row, i32::from(col) + d_col))
+        .filter(move |(row, col)| {
+            boundary == BoundaryMode::Toroidal
+                || (0..height).contains(row) && (0..width).contains(col)
+        })
+        .map(move |(row, col)| (row.rem_euclid(height) as u16, col.rem_euclid(width) as u16))
+}
+
+/// Buffer the next generation is written into by `Universe::tick` and `PackedUniverse::tick`,
+/// then swapped with the cells. Clones start without one: its contents don't matter.
+struct SpareCells<T = Cell>(Vec<T>);
+impl<T> Default for SpareCells<T> {
+    fn default() -> Self {
+        SpareCells(Vec::new())
+    }
+}
+impl<T> Clone for SpareCells<T> {
+    fn clone(&self) -> Self {
+        SpareCells::default()
+    }
+}
+impl<T> std::fmt::Debug for SpareCells<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SpareCells")
+    }
+}
 
 /// the `Universe` in which game plays. Represented as a `Vec` of `Cell`s.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// At most `u16::MAX` wide and high, indices are computed in `usize`.
+#[derive(Debug, Clone, Default)]
 pub struct Universe {
     pub area: Area,
     pub cells: Vec<Cell>,
     pub name: String,
+    /// number of `tick`s since creation
+    generation: u64,
+    rule: Rule,
+    /// rules overriding `rule` in some regions, the last matching one wins
+    regions: Vec<RegionRule>,
+    boundary: BoundaryMode,
+    neighbour_cache: NeighbourCache,
+    spare: SpareCells,
 }
+/// Same size, same cells: the name, the generation, the rules and the boundary don't matter
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        self.area == other.area && self.cells == other.cells
+    }
+}
+impl Eq for Universe {}
 impl<U1: Into<usize>, U2: Into<usize>> std::ops::Index<(U1, U2)> for Universe {
     type Output = Cell;
 
@@ -34,8 +218,22 @@ impl Universe {
             area,
             cells,
             name: name.to_string(),
+            generation: 0,
+            rule: Rule::default(),
+            regions: Vec::new(),
+            boundary: BoundaryMode::default(),
+            neighbour_cache: NeighbourCache::default(),
+            spare: SpareCells::default(),
         }
     }
+    /// Whether `tick` has neighbour indices computed, for any size
+    #[cfg(test)]
+    pub fn has_neighbour_cache(&self) -> bool {
+        !self.neighbour_cache.indices.is_empty()
+    }
+    pub fn builder() -> UniverseBuilder {
+        UniverseBuilder::default()
+    }
     pub fn with_name(self, name: impl ToString) -> Self {
         Self {
             name: name.to_string(),
@@ -45,17 +243,66 @@ impl Universe {
     pub fn name(&self) -> &str {
         &self.name
     }
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+    /// the rule to be used by the next `tick`s, starting with the very next one
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+    /// Use `rule` for the cells in `region` instead of the universe's own,
+    /// neighbours across the region's edges still count
+    pub fn add_region_rule(&mut self, region: Rect, rule: Rule) {
+        self.regions.push(RegionRule { region, rule });
+    }
+    pub fn region_rules(&self) -> &[RegionRule] {
+        &self.regions
+    }
+    pub fn clear_region_rules(&mut self) {
+        self.regions.clear();
+    }
+    /// The rule deciding the fate of the cell at (`row`;`col`)
+    pub fn rule_at(&self, row: u16, col: u16) -> &Rule {
+        self.regions
+            .iter()
+            .rev()
+            .find(|r| r.region.contains((col, row).into()))
+            .map_or(&self.rule, |r| &r.rule)
+    }
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+    pub fn set_boundary(&mut self, mode: BoundaryMode) {
+        self.boundary = mode;
+    }
+    /// whether (`row`;`col`) is on the absorbing edge of a `BoundaryMode::Dead` universe
+    pub fn is_void(&self, row: u16, col: u16) -> bool {
+        self.boundary == BoundaryMode::Dead
+            && (row == 0 || col == 0 || row + 1 == self.height() || col + 1 == self.width())
+    }
+    /// `Rule::parse` `s`, then `set_rule`
+    ///
+    /// # Errors
+    ///
+    /// if `s` is an invalid rule, the current one is kept then
+    pub fn apply_rule_string(&mut self, s: &str) -> Result<(), String> {
+        self.set_rule(Rule::parse(s)?);
+        Ok(())
+    }
 
     fn get_idx(&self, coord: (impl Into<usize>, impl Into<usize>)) -> usize {
         let row = coord.0.into();
         let col = coord.1.into();
         assert!(
-            (0..self.area.height).contains(&(row as u16)),
+            row < self.area.height.into(),
             "index out of range: len is {}, but index is {row}",
             self.area.height,
         );
         assert!(
-            (0..self.area.width).contains(&(col as u16)),
+            col < self.area.width.into(),
             "index out of range: len is {}, but index is {col}",
             self.area.width,
         );
@@ -69,27 +316,40 @@ impl Universe {
         idx
     }
 
-    pub fn live_neighbour_count(&self, row: u16, col: u16) -> u8 {
-        let mut sum = 0;
-
-        for delta_row in [self.area.height - 1, 0, 1] {
-            for delta_col in [self.area.width - 1, 0, 1] {
-                if delta_row == 0 && delta_col == 0 {
-                    continue;
-                }
+    /// The coordinates of the 8 neighbours of (`row`;`col`), see `neighbours`
+    fn neighbours(&self, row: u16, col: u16) -> impl Iterator<Item = (u16, u16)> {
+        neighbours(self.area, self.boundary, row, col)
+    }
 
-                let neighbour_row = (row + delta_row) % self.area.height;
-                let neighbour_col = (col + delta_col) % self.area.width;
+    pub fn live_neighbour_count(&self, row: u16, col: u16) -> u8 {
+        self.neighbours(row, col)
+            .filter(|&idx| self[idx] == Cell::Alive)
+            .count() as u8
+    }
 
-                sum += self[(neighbour_row, neighbour_col)] as u8;
-            }
-        }
-        sum
+    /// Whether (`row`;`col`) has at least `n` live neighbours, stopping as soon as it's known
+    pub fn neighbours_alive_at_least(&self, row: u16, col: u16, n: u8) -> bool {
+        n == 0
+            || self
+                .neighbours(row, col)
+                .filter(|&idx| self[idx] == Cell::Alive)
+                .nth(usize::from(n) - 1)
+                .is_some()
     }
 
-    /// Convert properly formatted Vec of Strings to Universe
-    fn from_vec_str(s: &[String]) -> Result<Self, String> {
-        let (metadata, pattern): (Vec<_>, Vec<_>) = s.iter().partition(|l| l.starts_with('!'));
+    /// Convert properly formatted lines to Universe
+    ///
+    /// # Errors
+    ///
+    /// if there are invalid characters, or no pattern lines at all
+    fn from_vec_str(s: &[impl AsRef<str>]) -> Result<Self, String> {
+        let (metadata, pattern): (Vec<_>, Vec<_>) = s
+            .iter()
+            .map(AsRef::as_ref)
+            .partition(|l| l.starts_with('!'));
+        if pattern.is_empty() {
+            return Err("parse error: empty pattern".into());
+        }
 
         let width = pattern
             .iter()
@@ -118,30 +378,272 @@ impl Universe {
         Ok(univ)
     }
 
-    /// Create universe with width, height: inserting shape into the middle
+    /// Parse RLE: `#N <name>` and other `#` comment lines, an optional
+    /// `x = .., y = .., rule = ..` header, then `<count><b|o|$>` runs ended by `!`.
+    /// The header is read leniently: spaces, order, trailing commas and missing fields don't matter,
+    /// `rule` may also be in `S/B` notation, missing dimensions are taken from the data.
     ///
     /// # Errors
     ///
-    /// if shape can't fit universe
-    pub fn from_figur(area: Area, figur: Universe) -> Result<Universe, ()> {
-        let count_alive = |univ: &Universe| -> usize {
-            univ.cells
-                .iter()
-                .filter(|cell| *cell == &Cell::Alive)
-                .count()
+    /// if the header or the data is invalid, the pattern doesn't fit into `x`×`y` or it's empty
+    pub fn from_rle(input: &str) -> Result<Self, String> {
+        let mut name = String::new();
+        let (mut width, mut height, mut rule) = (None, None, None);
+        let mut data = String::new();
+        for line in input.lines().map(str::trim) {
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(n) = comment.strip_prefix('N') {
+                    name = n.trim().to_string();
+                }
+            } else if data.is_empty() && line.contains('=') {
+                for field in line.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                    let (key, val) = field.split_once('=').ok_or_else(|| {
+                        format!("parse error: invalid RLE header field {field:?}")
+                    })?;
+                    let dim = || {
+                        val.trim()
+                            .parse::<u16>()
+                            .map_err(|e| format!("parse error: invalid RLE {}: {e}", key.trim()))
+                    };
+                    match key.trim().to_lowercase().as_str() {
+                        "x" => width = Some(dim()?),
+                        "y" => height = Some(dim()?),
+                        "rule" => rule = Some(val.trim().to_string()),
+                        _ => {}
+                    }
+                }
+            } else {
+                data += line;
+            }
+        }
+
+        let mut rows = vec![Vec::new()];
+        let mut count = String::new();
+        for ch in data.chars().filter(|ch| !ch.is_whitespace()) {
+            if ch.is_ascii_digit() {
+                count.push(ch);
+                continue;
+            }
+            let n = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse::<usize>()
+                    .map_err(|e| format!("parse error: invalid RLE run count: {e}"))?
+            };
+            count.clear();
+            match ch {
+                'b' => rows.last_mut().unwrap().extend(vec![Cell::Dead; n]),
+                'o' => rows.last_mut().unwrap().extend(vec![Cell::Alive; n]),
+                '$' => rows.extend(vec![Vec::new(); n]),
+                '!' => break,
+                _ => return Err(format!("parse error: {ch:?} is an invalid RLE character")),
+            }
+        }
+        // no trailing empty rows
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+        if rows.is_empty() && width.is_none() && height.is_none() {
+            return Err("parse error: empty pattern".into());
+        }
+
+        let data_width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let area = Area::new(
+            width.unwrap_or(data_width as u16),
+            height.unwrap_or(rows.len() as u16),
+        );
+        if data_width > area.width.into() || rows.len() > area.height.into() {
+            return Err(format!(
+                "parse error: RLE data ({data_width}x{}) doesn't fit into x = {}, y = {}",
+                rows.len(),
+                area.width,
+                area.height
+            ));
+        }
+
+        let mut univ = shapes::empty(area).with_name(name);
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &cell) in row.iter().enumerate() {
+                univ[(i, j)] = cell;
+            }
+        }
+        if let Some(rule) = rule {
+            // `S/B` notation, eg.: 23/3
+            let rule = match rule.split_once('/') {
+                Some((s, b)) if !rule.contains(['b', 'B']) => format!("B{b}/S{s}"),
+                _ => rule,
+            };
+            univ.apply_rule_string(&rule)?;
+        }
+        Ok(univ)
+    }
+
+    /// Parse either RLE or the `.cells` format
+    ///
+    /// # Errors
+    ///
+    /// if `s` is neither
+    pub fn parse(s: &str) -> Result<Universe, String> {
+        if s.trim_start().starts_with(['#', 'x']) {
+            Universe::from_rle(s)
+        } else {
+            s.parse()
+        }
+    }
+
+    /// Parse the plaintext `.cells` format: `!` comments, `O` alive, `.` dead,
+    /// short rows filled up with dead cells. Dead margins are cut off.
+    ///
+    /// # Errors
+    ///
+    /// if there's an invalid character, or nothing's alive
+    pub fn from_cells_format(input: &str) -> Result<Universe, String> {
+        let univ = input.parse::<Universe>()?;
+        if univ.population() == 0 {
+            return Err("parse error: no live cells".into());
+        }
+        Ok(univ.crop_to_content())
+    }
+
+    /// An `area` big universe with only the (`row`;`col`) `coords` alive
+    ///
+    /// # Errors
+    ///
+    /// if any of `coords` is out of range
+    pub fn from_coords(area: Area, coords: &[(u16, u16)]) -> Result<Universe, String> {
+        let mut univ = shapes::empty(area);
+        univ.check_in_range(coords)?;
+        for &(row, col) in coords {
+            univ[(row, col)] = Cell::Alive;
+        }
+        Ok(univ)
+    }
+
+    /// `rows` in the `.cells` format, in the middle of `area`, see `from_figur`
+    ///
+    /// ```ignore
+    /// let univ = Universe::from_str_rows(Area::new(8, 8), &["..O", "O.O", ".OO"])?;
+    /// assert_eq!(univ.population(), 5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// if `rows` are invalid, or don't fit into `area`
+    pub fn from_str_rows(area: Area, rows: &[&str]) -> Result<Universe, String> {
+        Universe::from_figur(area, Universe::from_vec_str(rows)?)
+    }
+
+    /// Read and `parse` the pattern at `path`, decompressing it if it's gzipped
+    ///
+    /// # Errors
+    ///
+    /// if it can't be read, or it's invalid
+    pub fn load(path: &Path) -> Result<Universe, String> {
+        let data = std::fs::read(path).map_err(|e| format!("couldn't read {path:?}: {e}"))?;
+        let data = gunzip(data).map_err(|e| format!("{path:?}: {e}"))?;
+        let s = String::from_utf8(data).map_err(|e| format!("{path:?} isn't UTF-8: {e}"))?;
+        Universe::parse(&s)
+    }
+
+    /// Write the whole grid to `path` in the `.cells` format, dead margins included
+    ///
+    /// # Errors
+    ///
+    /// if writing fails
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, format!("{self:#}"))
+    }
+
+    /// Draw the grid into a PNG at `path`: live cells are black `cell_px` sided squares on white
+    ///
+    /// # Errors
+    ///
+    /// if `cell_px` is 0, the image would be too big, or writing fails
+    #[cfg(feature = "image")]
+    pub fn to_png(&self, path: &Path, cell_px: u32) -> io::Result<()> {
+        let invalid = |why: &str| io::Error::new(io::ErrorKind::InvalidInput, why.to_string());
+        if cell_px == 0 {
+            return Err(invalid("cells should be at least 1 pixel wide"));
+        }
+        let size = |cells: u16| {
+            u32::from(cells)
+                .checked_mul(cell_px)
+                .filter(|&px| px <= i32::MAX as u32)
+        };
+        let (Some(width), Some(height)) = (size(self.width()), size(self.height())) else {
+            return Err(invalid("image would be too big"));
         };
+        let mut pixels = Vec::new();
+        pixels
+            .try_reserve_exact(width as usize * height as usize)
+            .map_err(|_| invalid("image would be too big"))?;
+        for row in self.cells.chunks(usize::from(self.width()).max(1)) {
+            let start = pixels.len();
+            for &cell in row {
+                let shade = if cell == Cell::Alive { 0 } else { 0xff };
+                pixels.extend(std::iter::repeat_n(shade, cell_px as usize));
+            }
+            for _ in 1..cell_px {
+                pixels.extend_from_within(start..start + width as usize);
+            }
+        }
+        std::fs::write(path, png::encode_gray(width, height, &pixels))
+    }
 
-        let figur_alive = count_alive(&figur);
+    /// Read a grid written by `save_to_path`.
+    /// Unlike `load`, every row has to be just as wide: nothing is filled in.
+    ///
+    /// # Errors
+    ///
+    /// if it can't be read, or it's empty or ragged
+    pub fn load_from_path(path: &Path) -> Result<Universe, String> {
+        let s =
+            std::fs::read_to_string(path).map_err(|e| format!("couldn't read {path:?}: {e}"))?;
+        let mut widths = s
+            .trim()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('!'))
+            .map(|line| line.chars().count());
+        let width = widths
+            .next()
+            .ok_or_else(|| format!("parse error: {path:?} is empty"))?;
+        if let Some((row, other)) = widths.enumerate().find(|&(_, w)| w != width) {
+            return Err(format!(
+                "parse error: row {} of {path:?} is {other} wide instead of {width}",
+                row + 2
+            ));
+        }
+        s.parse()
+    }
 
-        if area < figur.area {
-            return Err(());
+    /// Create universe with width, height: inserting shape into the middle, keeping its rule.
+    /// If it can't be exactly in the middle, the extra row or column of margin is at the bottom or the right.
+    ///
+    /// # Errors
+    ///
+    /// if shape can't fit universe
+    pub fn from_figur(area: Area, figur: Universe) -> Result<Universe, String> {
+        let figur_alive = figur.population();
+
+        if !area.contains(figur.area) {
+            return Err(format!(
+                "{} ({}x{}) doesn't fit into {}x{}",
+                figur.name(),
+                figur.width(),
+                figur.height(),
+                area.width,
+                area.height
+            ));
         }
 
         let mut univ = shapes::empty(area).with_name(figur.name());
+        univ.rule = figur.rule.clone();
 
         let (start_row, start_col) = (
-            (area.height - figur.height()) / 2,
-            (area.width - figur.width()) / 2,
+            centering_offset(area.height, figur.height()),
+            centering_offset(area.width, figur.width()),
         );
 
         let mut j = 0;
@@ -152,42 +654,764 @@ impl Universe {
             }
         }
 
-        assert_eq!(figur_alive, count_alive(&univ), "faulty algorithm");
+        assert_eq!(figur_alive, univ.population(), "faulty algorithm");
         Ok(univ)
     }
 
-    /// update life: `Universe`
-    pub fn tick(&mut self) {
+    /// Kill every cell and start counting generations over, the size is kept
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::Dead);
+        self.generation = 0;
+    }
+
+    /// Bring every cell to life and start counting generations over
+    pub fn fill_alive(&mut self) {
+        self.cells.fill(Cell::Alive);
+        self.generation = 0;
+    }
+
+    /// Toggle every cell, the generation stays the same
+    pub fn invert(&mut self) {
+        self.cells.iter_mut().for_each(Cell::toggle);
+    }
+
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    pub fn set_cell(&mut self, row: u16, col: u16, cell: Cell) {
+        self[(row, col)] = cell;
+    }
+
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    pub fn toggle_cell(&mut self, row: u16, col: u16) {
+        self[(row, col)].toggle();
+    }
+
+    /// Toggle all of `coords`, given as (`row`;`col`).
+    ///
+    /// # Errors
+    ///
+    /// if any of them is out of range, nothing is toggled then
+    pub fn toggle_cells(&mut self, coords: &[(u16, u16)]) -> Result<(), String> {
+        self.check_in_range(coords)?;
+        for &(row, col) in coords {
+            self[(row, col)].toggle();
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// if any of the (`row`;`col`) `coords` is out of range
+    pub fn check_in_range(&self, coords: &[(u16, u16)]) -> Result<(), String> {
+        match coords
+            .iter()
+            .find(|(row, col)| *row >= self.height() || *col >= self.width())
+        {
+            Some((row, col)) => Err(format!(
+                "({row};{col}) is out of range: universe is {}x{}",
+                self.width(),
+                self.height()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Change the area, keeping the cells centered: growing adds dead margins, shrinking drops them
+    pub fn resize(&mut self, area: Area) {
+        // (offset in the old, offset in the new, length to copy)
+        let overlap = |old: u16, new: u16| {
+            if new >= old {
+                (0, centering_offset(new, old), old)
+            } else {
+                (centering_offset(old, new), 0, new)
+            }
+        };
+        let (src_row, dst_row, rows) = overlap(self.height(), area.height);
+        let (src_col, dst_col, cols) = overlap(self.width(), area.width);
+
+        let mut cells = vec![Cell::Dead; area.len()];
+        for row in 0..rows {
+            for col in 0..cols {
+                let dst = (dst_row + row) as usize * area.width as usize + (dst_col + col) as usize;
+                cells[dst] = self[(src_row + row, src_col + col)];
+            }
+        }
+        self.cells = cells;
+        self.area = area;
+    }
+
+    /// Copy the live cells of `other` onto `self`, with `other`'s top-left corner at
+    /// (`at_row`;`at_col`). Live cells of `self` are kept, parts falling outside are clipped.
+    pub fn stamp(&mut self, other: &Universe, at_row: u16, at_col: u16) {
+        for row in 0..other.height() {
+            for col in 0..other.width() {
+                let (r, c) = (
+                    at_row as usize + row as usize,
+                    at_col as usize + col as usize,
+                );
+                if r >= self.height() as usize || c >= self.width() as usize {
+                    continue;
+                }
+                self[(r, c)] = self[(r, c)] | other[(row, col)];
+            }
+        }
+    }
+
+    /// Same as `stamp`, but leaves `self` untouched and errors with the first collision
+    /// if any live cell of `other` would land on a live cell of `self`.
+    ///
+    /// # Errors
+    ///
+    /// if the live cells overlap
+    pub fn stamp_checked(
+        &mut self,
+        other: &Universe,
+        at_row: u16,
+        at_col: u16,
+    ) -> Result<(), Overlap> {
+        for row in 0..other.height() {
+            for col in 0..other.width() {
+                let (r, c) = (
+                    at_row as usize + row as usize,
+                    at_col as usize + col as usize,
+                );
+                if r >= self.height() as usize || c >= self.width() as usize {
+                    continue;
+                }
+                if (self[(r, c)] & other[(row, col)]) == Cell::Alive {
+                    return Err(Overlap {
+                        row: r as u16,
+                        col: c as u16,
+                    });
+                }
+            }
+        }
+        self.stamp(other, at_row, at_col);
+        Ok(())
+    }
+
+    /// The smallest `Rect` containing every live cell, `x` being the column, `y` the row.
+    /// `None` if there are no live cells.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let mut alive = (0..self.height())
+            .flat_map(|row| (0..self.width()).map(move |col| (row, col)))
+            .filter(|&idx| self[idx] == Cell::Alive);
+        let (row, col) = alive.next()?;
+        let (mut top, mut bottom, mut left, mut right) = (row, row, col, col);
+        for (row, col) in alive {
+            top = top.min(row);
+            bottom = bottom.max(row);
+            left = left.min(col);
+            right = right.max(col);
+        }
+        Some(Rect::new(left, top, right - left + 1, bottom - top + 1))
+    }
+
+    /// The cells inside `region`, clipped to the bounds of `self`, `x` being the column, `y` the row.
+    /// The name and the rule are kept.
+    pub fn sub_universe(&self, region: Rect) -> Universe {
+        let region = region.intersection(Rect::new(0, 0, self.width(), self.height()));
+        let mut univ = shapes::empty(Area::new(region.width, region.height)).with_name(self.name());
+        univ.set_rule(self.rule.clone());
+        for row in 0..region.height {
+            for col in 0..region.width {
+                univ[(row, col)] = self[(region.y + row, region.x + col)];
+            }
+        }
+        univ
+    }
+
+    /// Cut off the dead margins: the result is exactly as big as the `bounding_box`.
+    pub fn crop_to_content(&self) -> Universe {
+        self.sub_universe(self.bounding_box().unwrap_or_default())
+    }
+
+    /// The whole grid turned a quarter clockwise: width and height are swapped.
+    pub fn rotate_cw(&self) -> Universe {
+        let area = Area::new(self.height(), self.width());
+        let mut univ = shapes::empty(area).with_name(self.name());
+        univ.set_rule(self.rule.clone());
+        univ.boundary = self.boundary;
+        for row in 0..area.height {
+            for col in 0..area.width {
+                univ[(row, col)] = self[(self.height() - 1 - col, row)];
+            }
+        }
+        univ
+    }
+
+    /// The live cells turned a quarter clockwise, in the middle of a grid
+    /// as big as this one, grown if the rotated pattern doesn't fit.
+    pub fn rotate_cw_fit(&self) -> Universe {
+        let rotated = self.crop_to_content().rotate_cw();
+        let mut univ = Universe::from_figur(self.area.union(rotated.area), rotated)
+            .expect("area is made to fit the figure");
+        univ.boundary = self.boundary;
+        univ
+    }
+
+    /// Shift every cell `d_row` rows down and `d_col` columns right, wrapping around the edges.
+    /// On a torus without region rules, it doesn't change how the pattern evolves.
+    pub fn roll(&mut self, d_row: i32, d_col: i32) {
+        let (height, width) = (i32::from(self.height()), i32::from(self.width()));
+        let mut cells = vec![Cell::Dead; self.cells.len()];
+        for row in 0..height {
+            for col in 0..width {
+                let to_row = (row + d_row).rem_euclid(height);
+                let to_col = (col + d_col).rem_euclid(width);
+                cells[(to_row * width + to_col) as usize] =
+                    self.cells[(row * width + col) as usize];
+            }
+        }
+        self.cells = cells;
+    }
+
+    /// Groups of live cells touching each other, even diagonally.
+    /// The edges don't wrap here, a pattern crossing them falls apart.
+    pub fn components(&self) -> Vec<Vec<(u16, u16)>> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut components = Vec::new();
+        for start in 0..self.cells.len() {
+            if seen[start] || self.cells[start] == Cell::Dead {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = Vec::new();
+            let mut todo = vec![start];
+            while let Some(idx) = todo.pop() {
+                let (row, col) = (idx / self.width() as usize, idx % self.width() as usize);
+                component.push((row as u16, col as u16));
+                for r in row.saturating_sub(1)..=(row + 1).min(self.height() as usize - 1) {
+                    for c in col.saturating_sub(1)..=(col + 1).min(self.width() as usize - 1) {
+                        let idx = self.get_idx((r, c));
+                        if !seen[idx] && self.cells[idx] == Cell::Alive {
+                            seen[idx] = true;
+                            todo.push(idx);
+                        }
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Mean (row;col) of `cells`, `None` if empty
+    pub fn center_of_mass(cells: &[(u16, u16)]) -> Option<(f64, f64)> {
+        if cells.is_empty() {
+            return None;
+        }
+        let n = cells.len() as f64;
+        let (rows, cols) = cells.iter().fold((0., 0.), |(rows, cols), &(row, col)| {
+            (rows + f64::from(row), cols + f64::from(col))
+        });
+        Some((rows / n, cols / n))
+    }
+
+    /// number of `Cell::Alive`s, recomputed on each call
+    pub fn population(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&&cell| cell == Cell::Alive)
+            .count()
+    }
+
+    /// The cells, row by row
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// The cell at (`row`;`col`), `Cell::Dead` if it's out of range,
+    /// as if the universe was surrounded by nothing
+    pub fn cell_at(&self, row: u16, col: u16) -> Cell {
+        if row < self.height() && col < self.width() {
+            self[(row, col)]
+        } else {
+            Cell::Dead
+        }
+    }
+
+    /// An owned copy of the cells, to be given back to `restore_cells`
+    pub fn cells_snapshot(&self) -> Vec<Cell> {
+        self.cells.clone()
+    }
+
+    /// Replace the cells with a `cells_snapshot`, the generation and the rule are kept
+    ///
+    /// # Errors
+    ///
+    /// if `cells` isn't as long as the universe is big, nothing changes then
+    pub fn restore_cells(&mut self, cells: Vec<Cell>) -> Result<(), String> {
+        let len = usize::from(self.width()) * usize::from(self.height());
+        if cells.len() != len {
+            return Err(format!(
+                "{} cells don't fit into {}x{}",
+                cells.len(),
+                self.width(),
+                self.height()
+            ));
+        }
+        self.cells = cells;
+        Ok(())
+    }
+
+    /// number of (`Cell::Alive`s, `Cell::Dead`s), counted in one pass
+    pub fn counts(&self) -> (usize, usize) {
+        self.cells
+            .iter()
+            .fold((0, 0), |(alive, dead), &cell| match cell {
+                Cell::Alive => (alive + 1, dead),
+                Cell::Dead => (alive, dead + 1),
+            })
+    }
+
+    /// number of cells differing from `other`
+    ///
+    /// # Panics
+    ///
+    /// if the areas differ
+    pub fn diff_count(&self, other: &Universe) -> usize {
+        assert_eq!(
+            self.area, other.area,
+            "can't diff universes of different areas"
+        );
+        self.cells
+            .iter()
+            .zip(&other.cells)
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    /// Bounding boxes of the groups of cells differing between `self` and `other`,
+    /// `x` being the column, `y` the row. Cells count as a group like in `components`.
+    ///
+    /// # Panics
+    ///
+    /// if the areas differ
+    pub fn diff_regions(&self, other: &Universe) -> Vec<Rect> {
+        assert_eq!(
+            self.area, other.area,
+            "can't diff universes of different areas"
+        );
+        let cells = self.cells.iter().zip(&other.cells).map(|(&a, &b)| a ^ b);
+        let changed = Universe::new(self.area, cells.collect(), "diff");
+        changed
+            .components()
+            .iter()
+            .map(|component| {
+                let (mut top, mut left) = (u16::MAX, u16::MAX);
+                let (mut bottom, mut right) = (0, 0);
+                for &(row, col) in component {
+                    top = top.min(row);
+                    bottom = bottom.max(row);
+                    left = left.min(col);
+                    right = right.max(col);
+                }
+                Rect::new(left, top, right - left + 1, bottom - top + 1)
+            })
+            .collect()
+    }
+
+    /// share of live cells, `0.0` for an empty area
+    pub fn live_ratio(&self) -> f64 {
+        if self.area.is_empty() {
+            return 0.0;
+        }
+        self.population() as f64 / self.area.len() as f64
+    }
+
+    /// The smallest tile that repeated, fills the board, eg. for an agar.
+    /// The whole area if there's no smaller one.
+    pub fn minimal_period_tile(&self) -> Area {
+        let (w, h) = (self.width(), self.height());
+        // added in u32: sides above `i16::MAX` would overflow
+        let wrap =
+            |i: u16, d: u16, len: u16| ((u32::from(i) + u32::from(d)) % u32::from(len)) as u16;
+        let repeats = |dr: u16, dc: u16| {
+            (0..h).all(|row| {
+                (0..w).all(|col| self[(row, col)] == self[(wrap(row, dr, h), wrap(col, dc, w))])
+            })
+        };
+        let width = (1..=w)
+            .find(|&tw| w % tw == 0 && repeats(0, tw))
+            .unwrap_or(w);
+        let height = (1..=h)
+            .find(|&th| h % th == 0 && repeats(th, 0))
+            .unwrap_or(h);
+        Area::new(width, height)
+    }
+
+    /// Hash of the size and the cells: the same for equal patterns, different ones may collide
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.area.hash(&mut hasher);
+        self.cells.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Checksums of the cropped content of the first `n` generations, starting with the current one.
+    /// Independent of the position of the pattern, so it can identify it.
+    pub fn fingerprint_sequence(&self, n: usize) -> Vec<u64> {
+        let mut univ = self.clone();
+        (0..n)
+            .map(|i| {
+                if i > 0 {
+                    univ.tick();
+                }
+                univ.crop_to_content().state_hash()
+            })
+            .collect()
+    }
+
+    /// Tick `generations` times, calling `progress` after each
+    pub fn run_for_with_progress(&mut self, generations: u64, mut progress: impl FnMut(&Universe)) {
+        for _ in 0..generations {
+            self.tick();
+            progress(self);
+        }
+    }
+
+    /// Tick until more than `threshold` cells change in one generation, the pattern
+    /// stabilizes or dies out, or `max` generations pass. Returns the number of ticks.
+    pub fn run_until_delta(&mut self, threshold: usize, max: u64) -> u64 {
+        for ticks in 1..=max {
+            let prev = self.clone();
+            let outcome = self.tick();
+            if self.diff_count(&prev) > threshold || outcome != TickOutcome::Changed {
+                return ticks;
+            }
+        }
+        max
+    }
+
+    /// Total number of cells changing state until the pattern stabilizes or dies out,
+    /// or `max` generations pass
+    pub fn count_transitions_to_stable(&mut self, max: u64) -> u64 {
+        let mut transitions = 0;
+        for _ in 0..max {
+            let prev = self.clone();
+            let outcome = self.tick();
+            transitions += self.diff_count(&prev) as u64;
+            if outcome != TickOutcome::Changed {
+                break;
+            }
+        }
+        transitions
+    }
+
+    /// (generation, population) now and after each of the next `generations` ticks
+    pub fn population_series(&mut self, generations: u64) -> Vec<(u64, usize)> {
+        let mut series = Vec::with_capacity(generations as usize + 1);
+        series.push((self.generation, self.population()));
+        self.run_for_with_progress(generations, |univ| {
+            series.push((univ.generation, univ.population()));
+        });
+        series
+    }
+
+    /// Whether the next generation is the same as this one: a still life, or nothing at all
+    pub fn is_stable(&self) -> bool {
         let mut next = self.clone();
+        next.tick();
+        next.cells == self.cells
+    }
+
+    /// The smallest period up to `max` after which the cells are the same as now,
+    /// `Some(1)` for still lifes. Unlike `measure_longevity`, only the current state counts.
+    pub fn detect_period(&self, max: u64) -> Option<u64> {
+        let mut next = self.clone();
+        (1..=max).find(|_| {
+            next.tick();
+            next.cells == self.cells
+        })
+    }
+
+    /// Tick until a previous state comes back or `max` generations have passed.
+    /// States are compared by their hash, so a collision could end the run early.
+    pub fn measure_longevity(&mut self, max: u64) -> Longevity {
+        let mut seen = HashMap::from([(self.state_hash(), 0)]);
+        let mut peak_population = self.population();
 
-        for row in 0..self.height() {
-            for col in 0..self.width() {
-                let idx = (row, col);
-                let cell = self[idx];
-                let live_neighbours = self.live_neighbour_count(row, col);
-
-                let next_cell = match (cell, live_neighbours) {
-                    // Rule 1: Any live cell with fewer than two live neighbours
-                    // dies, as if caused by underpopulation.
-                    (Cell::Alive, n) if n < 2 => Cell::Dead,
-                    // Rule 2: Any live cell with two or three live neighbours
-                    // lives on to the next generation.
-                    (Cell::Alive, 2 | 3) => Cell::Alive,
-                    // Rule 3: Any live cell with more than three live
-                    // neighbours dies, as if by overpopulation.
-                    (Cell::Alive, n) if n > 3 => Cell::Dead,
-                    // Rule 4: Any dead cell with exactly three live neighbours
-                    // becomes a live cell, as if by reproduction.
-                    (Cell::Dead, 3) => Cell::Alive,
-                    // All other cells remain in the same state.
-                    (otherwise, _) => otherwise,
+        for gen in 1..=max {
+            self.tick();
+            peak_population = peak_population.max(self.population());
+            if let Some(first) = seen.insert(self.state_hash(), gen) {
+                return Longevity {
+                    generations: first,
+                    period: Some(gen - first),
+                    peak_population,
+                    final_population: self.population(),
                 };
+            }
+        }
+        Longevity {
+            generations: max,
+            period: None,
+            peak_population,
+            final_population: self.population(),
+        }
+    }
+
+    /// Write the whole state compactly: `BINARY_MAGIC`, `BINARY_VERSION`, then little-endian
+    /// width and height: `u16`, birth and survival neighbour counts as bitmasks: `u16`,
+    /// boundary: `u8` (0: toroidal, 1: dead), generation: `u64`, name length: `u16` and UTF-8 bytes,
+    /// finally the cells, 8 in a byte, lowest bit first. Region rules aren't saved.
+    ///
+    /// # Errors
+    ///
+    /// if writing fails, or the name is longer than `u16::MAX` bytes
+    pub fn save_binary(&self, mut w: impl io::Write) -> io::Result<()> {
+        let mask = |counts: &[u8]| counts.iter().fold(0u16, |mask, &n| mask | 1 << n);
+        let name = u16::try_from(self.name.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "name is too long"))?;
+        w.write_all(BINARY_MAGIC)?;
+        w.write_all(&[BINARY_VERSION])?;
+        w.write_all(&self.width().to_le_bytes())?;
+        w.write_all(&self.height().to_le_bytes())?;
+        w.write_all(&mask(&self.rule.birth).to_le_bytes())?;
+        w.write_all(&mask(&self.rule.survival).to_le_bytes())?;
+        w.write_all(&[u8::from(self.boundary == BoundaryMode::Dead)])?;
+        w.write_all(&self.generation.to_le_bytes())?;
+        w.write_all(&name.to_le_bytes())?;
+        w.write_all(self.name.as_bytes())?;
+        let cells = self
+            .cells
+            .chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .enumerate()
+                    .fold(0u8, |b, (i, &cell)| b | u8::from(bool::from(cell)) << i)
+            })
+            .collect::<Vec<_>>();
+        w.write_all(&cells)
+    }
+
+    /// Read what `save_binary` wrote
+    ///
+    /// # Errors
+    ///
+    /// if reading fails, the magic bytes or the version don't match, or the data is invalid
+    pub fn load_binary(mut r: impl Read) -> Result<Universe, String> {
+        let mut read = |len: usize| {
+            let mut buf = vec![0; len];
+            r.read_exact(&mut buf)
+                .map(|()| buf)
+                .map_err(|e| format!("invalid binary universe: {e}"))
+        };
+        let magic = read(4)?;
+        if magic != BINARY_MAGIC {
+            return Err(format!(
+                "not a binary universe: starts with {magic:?} instead of {BINARY_MAGIC:?}"
+            ));
+        }
+        let version = read(1)?[0];
+        if version != BINARY_VERSION {
+            return Err(format!(
+                "unsupported binary universe version {version}, only {BINARY_VERSION} is supported"
+            ));
+        }
+        let le16 = |b: Vec<u8>| u16::from_le_bytes([b[0], b[1]]);
+        let area = Area::new(le16(read(2)?), le16(read(2)?));
+        let counts = |mask: u16| (0..=8).filter(|n| mask & 1 << n != 0).collect::<Vec<u8>>();
+        let rule = Rule {
+            birth: counts(le16(read(2)?)),
+            survival: counts(le16(read(2)?)),
+        };
+        let boundary = match read(1)?[0] {
+            0 => BoundaryMode::Toroidal,
+            1 => BoundaryMode::Dead,
+            other => return Err(format!("invalid boundary mode {other} in binary universe")),
+        };
+        let generation = u64::from_le_bytes(read(8)?.try_into().expect("read 8 bytes"));
+        let name_len = le16(read(2)?);
+        let name = String::from_utf8(read(name_len.into())?)
+            .map_err(|e| format!("invalid name in binary universe: {e}"))?;
+        // read as it comes instead of trusting the header with a big allocation
+        let len = area.len().div_ceil(8);
+        let mut packed = Vec::new();
+        r.take(len as u64)
+            .read_to_end(&mut packed)
+            .map_err(|e| format!("invalid binary universe: {e}"))?;
+        if packed.len() != len {
+            return Err(format!(
+                "invalid binary universe: {len} bytes of cells expected, only got {}",
+                packed.len()
+            ));
+        }
+        let cells = (0..area.len())
+            .map(|i| Cell::from(packed[i / 8] & 1 << (i % 8) != 0))
+            .collect();
+
+        let mut univ = Universe::new(area, cells, name);
+        univ.rule = rule;
+        univ.boundary = boundary;
+        univ.generation = generation;
+        Ok(univ)
+    }
+
+    /// Encode as RLE: `#N <name>`, the `x = .., y = .., rule = ..` header,
+    /// then the rows with trailing dead cells trimmed, ended by `!`
+    pub fn to_rle(&self) -> String {
+        /// max line length recommended by the format
+        const LINE_LEN: usize = 70;
+
+        let run = |n: usize, tag: char| {
+            if n == 1 {
+                tag.to_string()
+            } else {
+                format!("{n}{tag}")
+            }
+        };
+        let mut items = Vec::new();
+        let mut last_row = None;
+        for (i, row) in self.cells.chunks(self.width().max(1).into()).enumerate() {
+            let Some(end) = row.iter().rposition(|&cell| cell == Cell::Alive) else {
+                continue;
+            };
+            let newlines = last_row.map_or(i, |last| i - last);
+            if newlines > 0 {
+                items.push(run(newlines, '$'));
+            }
+            last_row = Some(i);
+
+            for chunk in row[..=end].chunk_by(|a, b| a == b) {
+                let tag = if chunk[0] == Cell::Alive { 'o' } else { 'b' };
+                items.push(run(chunk.len(), tag));
+            }
+        }
+        items.push("!".into());
+
+        let mut rle = String::new();
+        if !self.name.is_empty() {
+            rle += &format!("#N {}\n", self.name);
+        }
+        rle += &format!(
+            "x = {}, y = {}, rule = {}\n",
+            self.width(),
+            self.height(),
+            self.rule
+        );
+        let mut line_len = 0;
+        for item in items {
+            if line_len + item.len() > LINE_LEN {
+                rle.push('\n');
+                line_len = 0;
+            }
+            line_len += item.len();
+            rle += &item;
+        }
+        rle.push('\n');
+        rle
+    }
 
-                next[idx] = next_cell;
+    /// Draw in a rounded box, each cell with `glyphs`, 2 columns wide
+    pub fn render(&self, glyphs: Glyphs) -> String {
+        // `Glyphs::cell` is always 2 columns wide, the corners take the place of the sides
+        let border = "─".repeat(self.width() as usize * 2);
+        let mut s = format!("╭{border}╮\r\n");
+        for line in self.cells.chunks(self.width().max(1).into()) {
+            s.push('│');
+            for &cell in line {
+                s += &glyphs.cell(cell);
             }
+            s += "│\r\n";
         }
+        s += &format!("╰{border}╯\r\n");
+        s
+    }
 
-        *self = next;
+    /// Write the cropped state as RLE to `dir/gen_<generation>.rle` every `every` generations,
+    /// ticking `generations` times. Returns the number of files written.
+    ///
+    /// # Errors
+    ///
+    /// if `every` is 0, or writing to `dir` fails
+    pub fn export_rle_frames(
+        &mut self,
+        dir: &Path,
+        generations: u64,
+        every: u64,
+    ) -> io::Result<usize> {
+        if every == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "can't export every 0th generation",
+            ));
+        }
+        std::fs::create_dir_all(dir)?;
+        let mut written = 0;
+        for i in 0..=generations {
+            if i % every == 0 {
+                let path = dir.join(format!("gen_{:05}.rle", self.generation));
+                std::fs::write(path, self.crop_to_content().to_rle())?;
+                written += 1;
+            }
+            if i < generations {
+                self.tick();
+            }
+        }
+        Ok(written)
+    }
+
+    /// update life: `Universe`, according to its `Rule`
+    /// With the `parallel` feature, big universes tick in all available threads
+    pub fn tick(&mut self) -> TickOutcome {
+        let threads = if cfg!(feature = "parallel") && self.cells.len() >= PARALLEL_MIN_CELLS {
+            std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+        } else {
+            1
+        };
+        self.tick_in_threads(threads)
+    }
+
+    /// `tick`, computing the next generation in `threads` threads, each taking whole rows.
+    /// The result is the same for any number of threads.
+    pub fn tick_in_threads(&mut self, threads: usize) -> TickOutcome {
+        self.neighbour_cache.update(self.area, self.boundary);
+        let mut next = std::mem::take(&mut self.spare.0);
+        next.clear();
+        next.resize(self.cells.len(), Cell::Dead);
+        let width = usize::from(self.width()).max(1);
+        let rows = usize::from(self.height()).div_ceil(threads.max(1)).max(1);
+        if threads <= 1 {
+            self.next_cells(0, &mut next);
+        } else {
+            let univ = &*self;
+            std::thread::scope(|scope| {
+                for (i, chunk) in next.chunks_mut(rows * width).enumerate() {
+                    scope.spawn(move || univ.next_cells(i * rows * width, chunk));
+                }
+            });
+        }
+
+        self.generation += 1;
+        let outcome = if next.iter().all(|&cell| cell == Cell::Dead) {
+            TickOutcome::Extinct
+        } else if next == self.cells {
+            TickOutcome::Stable
+        } else {
+            TickOutcome::Changed
+        };
+        // the current cells are the spare buffer of the next tick
+        self.spare.0 = std::mem::replace(&mut self.cells, next);
+        outcome
+    }
+
+    /// Write the next state of the cells from index `start` on into `next`
+    fn next_cells(&self, start: usize, next: &mut [Cell]) {
+        let width = usize::from(self.width()).max(1);
+        for (idx, next) in (start..).zip(next) {
+            let (row, col) = ((idx / width) as u16, (idx % width) as u16);
+            *next = self
+                .neighbour_cache
+                .next_state(idx, self.rule_at(row, col), |n| {
+                    self.cells[n] == Cell::Alive
+                });
+        }
     }
 
     pub fn width(&self) -> u16 {
@@ -198,16 +1422,66 @@ impl Universe {
         self.area.height
     }
 }
+/// Builds a `Universe` step by step, by default a 32x32 one, dead, with B3/S23 on a torus:
+///
+/// ```ignore
+/// let univ = Universe::builder().width(8).rule(rule).fill(Cell::Alive).build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniverseBuilder {
+    area: Area,
+    name: String,
+    rule: Rule,
+    boundary: BoundaryMode,
+    fill: Cell,
+}
+impl Default for UniverseBuilder {
+    fn default() -> Self {
+        Self {
+            area: Area::new(32, 32),
+            name: String::new(),
+            rule: Rule::default(),
+            boundary: BoundaryMode::default(),
+            fill: Cell::Dead,
+        }
+    }
+}
+impl UniverseBuilder {
+    pub fn width(self, width: u16) -> Self {
+        let area = Area::new(width, self.area.height);
+        Self { area, ..self }
+    }
+    pub fn height(self, height: u16) -> Self {
+        let area = Area::new(self.area.width, height);
+        Self { area, ..self }
+    }
+    pub fn name(self, name: impl ToString) -> Self {
+        let name = name.to_string();
+        Self { name, ..self }
+    }
+    pub fn rule(self, rule: Rule) -> Self {
+        Self { rule, ..self }
+    }
+    pub fn boundary(self, boundary: BoundaryMode) -> Self {
+        Self { boundary, ..self }
+    }
+    /// the state of every cell
+    pub fn fill(self, fill: Cell) -> Self {
+        Self { fill, ..self }
+    }
+    pub fn build(self) -> Universe {
+        let mut univ = Universe::new(self.area, vec![self.fill; self.area.len()], self.name);
+        univ.rule = self.rule;
+        univ.boundary = self.boundary;
+        univ
+    }
+}
+
 impl std::str::FromStr for Universe {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v = s
-            .trim()
-            .lines()
-            .map(str::trim)
-            .map(std::convert::Into::into)
-            .collect::<Vec<String>>();
+        let v = s.trim().lines().map(str::trim).collect::<Vec<_>>();
         Self::from_vec_str(&v)
     }
 }
@@ -218,6 +1492,9 @@ impl Shape for Universe {
             for x in 0..self.width() {
                 match self[(y, x)] {
                     Cell::Alive => painter.paint(x.into(), y.into(), Color::White),
+                    Cell::Dead if self.is_void(y, x) => {
+                        painter.paint(x.into(), y.into(), Color::DarkGray);
+                    }
                     Cell::Dead => continue,
                 }
             }
@@ -225,17 +1502,40 @@ impl Shape for Universe {
     }
 }
 
+/// `{:#}` writes it in the `.cells` format
 impl std::fmt::Display for Universe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "╭{}╮\r", "─".repeat(self.width() as usize * 2))?;
-        for line in self.cells.as_slice().chunks(self.width() as usize) {
-            write!(f, "│")?;
-            for &cell in line {
-                let symbol = if cell == Cell::Dead { '◻' } else { '◼' }; // ◻
-                write!(f, "{symbol} ")?;
+        if f.alternate() {
+            writeln!(f, "!Name: {}", self.name)?;
+            for line in self.cells.chunks(self.width().max(1).into()) {
+                let line = line
+                    .iter()
+                    .map(|&cell| char::from(cell))
+                    .collect::<String>();
+                writeln!(f, "{line}")?;
             }
-            writeln!(f, "│\r")?;
+            return Ok(());
         }
-        writeln!(f, "╰{}╯\r", "─".repeat(self.width() as usize * 2))
+        write!(f, "{}", self.render(Glyphs::default()))
+    }
+}
+
+/// `data`, decompressed if it's gzipped
+#[cfg(feature = "gzip")]
+fn gunzip(data: Vec<u8>) -> Result<Vec<u8>, String> {
+    if gzip::is_gzip(&data) {
+        gzip::decompress(&data)
+    } else {
+        Ok(data)
+    }
+}
+
+/// `data`, refused if it's gzipped: that needs the `gzip` feature
+#[cfg(not(feature = "gzip"))]
+fn gunzip(data: Vec<u8>) -> Result<Vec<u8>, String> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        Err("gzipped, reading it needs the gzip feature".into())
+    } else {
+        Ok(data)
     }
 }
@@ -0,0 +1,266 @@
+use super::{BoundaryMode, SpareCells, TickOutcome, Universe};
+use crate::app::{Area, Cell, Rule};
+
+/// Bits in a word of `PackedUniverse::bits`
+const WORD: usize = u64::BITS as usize;
+
+/// A `Universe` taking a bit a cell instead of a byte: a 1024x1024 one is 128KiB.
+/// Evolves just the same, but can't have region rules.
+#[derive(Debug, Clone)]
+pub struct PackedUniverse {
+    area: Area,
+    /// the cells row by row, lowest bit first, 1 meaning alive.
+    /// Every row starts a new word, the bits after its last cell are 0.
+    bits: Vec<u64>,
+    name: String,
+    generation: u64,
+    rule: Rule,
+    boundary: BoundaryMode,
+    spare: SpareCells<u64>,
+}
+/// Same as for `Universe`: same size, same cells
+impl PartialEq for PackedUniverse {
+    fn eq(&self, other: &Self) -> bool {
+        self.area == other.area && self.bits == other.bits
+    }
+}
+impl Eq for PackedUniverse {}
+
+impl TryFrom<&Universe> for PackedUniverse {
+    type Error = String;
+
+    /// # Errors
+    ///
+    /// if `univ` has region rules, they'd be lost
+    fn try_from(univ: &Universe) -> Result<Self, Self::Error> {
+        if !univ.regions.is_empty() {
+            return Err("can't pack a universe with region rules".into());
+        }
+        let mut packed = PackedUniverse {
+            area: univ.area,
+            bits: vec![0; row_words(univ.area) * usize::from(univ.area.height)],
+            name: univ.name.clone(),
+            generation: univ.generation,
+            rule: univ.rule.clone(),
+            boundary: univ.boundary,
+            spare: SpareCells::default(),
+        };
+        for row in 0..univ.height() {
+            for col in 0..univ.width() {
+                packed.set_cell(row, col, univ[(row, col)]);
+            }
+        }
+        Ok(packed)
+    }
+}
+
+impl PackedUniverse {
+    /// Unpack into a `Universe`
+    pub fn to_universe(&self) -> Universe {
+        let cells = (0..self.area.height)
+            .flat_map(|row| (0..self.area.width).map(move |col| (row, col)))
+            .map(|(row, col)| self.cell_at(row, col))
+            .collect();
+        let mut univ = Universe::new(self.area, cells, &self.name);
+        univ.generation = self.generation;
+        univ.rule = self.rule.clone();
+        univ.boundary = self.boundary;
+        univ
+    }
+
+    /// Word and bit of (`row`;`col`) in `bits`
+    ///
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    fn get_index(&self, row: u16, col: u16) -> (usize, u32) {
+        assert!(
+            row < self.area.height && col < self.area.width,
+            "index out of range: ({row};{col}) isn't in {}x{}",
+            self.area.width,
+            self.area.height
+        );
+        let col = usize::from(col);
+        (
+            usize::from(row) * row_words(self.area) + col / WORD,
+            (col % WORD) as u32,
+        )
+    }
+
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    pub fn cell_at(&self, row: u16, col: u16) -> Cell {
+        let (word, bit) = self.get_index(row, col);
+        (self.bits[word] >> bit & 1 == 1).into()
+    }
+
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    pub fn set_cell(&mut self, row: u16, col: u16, cell: Cell) {
+        let (word, bit) = self.get_index(row, col);
+        match cell {
+            Cell::Alive => self.bits[word] |= 1 << bit,
+            Cell::Dead => self.bits[word] &= !(1 << bit),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// if (`row`;`col`) is out of range
+    pub fn toggle_cell(&mut self, row: u16, col: u16) {
+        let (word, bit) = self.get_index(row, col);
+        self.bits[word] ^= 1 << bit;
+    }
+
+    pub fn population(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Same as `Universe::tick`, but 64 cells at a time: the neighbours are counted
+    /// by adding the shifted words of the rows above, at and below.
+    pub fn tick(&mut self) -> TickOutcome {
+        let (height, words) = (usize::from(self.area.height), row_words(self.area));
+        let wrap = self.boundary == BoundaryMode::Toroidal;
+        let row = |r: usize| &self.bits[r * words..(r + 1) * words];
+        let mut next = std::mem::take(&mut self.spare.0);
+        next.clear();
+        next.resize(self.bits.len(), 0);
+
+        for r in 0..height {
+            let above = match r {
+                0 if wrap => Some(height - 1),
+                0 => None,
+                _ => Some(r - 1),
+            };
+            let below = match r + 1 {
+                down if down < height => Some(down),
+                _ if wrap => Some(0),
+                _ => None,
+            };
+            for k in 0..words {
+                let [a_west, a, a_east] = above.map_or([0; 3], |above| self.around(row(above), k));
+                let [west, alive, east] = self.around(row(r), k);
+                let [b_west, b, b_east] = below.map_or([0; 3], |below| self.around(row(below), k));
+                let counts = [a_west, a, a_east, west, east, b_west, b, b_east]
+                    .into_iter()
+                    .fold([0; 4], add_bit);
+                let count_is = |n: &u8| {
+                    (0..4).fold(u64::MAX, |acc, i| {
+                        acc & if n >> i & 1 == 1 {
+                            counts[i]
+                        } else {
+                            !counts[i]
+                        }
+                    })
+                };
+                let born = self
+                    .rule
+                    .birth
+                    .iter()
+                    .map(count_is)
+                    .fold(0, |acc, m| acc | m);
+                let stay = self
+                    .rule
+                    .survival
+                    .iter()
+                    .map(count_is)
+                    .fold(0, |acc, m| acc | m);
+                let mask = if k + 1 == words {
+                    last_word_mask(self.area)
+                } else {
+                    u64::MAX
+                };
+                next[r * words + k] = (born & !alive | stay & alive) & mask;
+            }
+        }
+
+        self.generation += 1;
+        let outcome = if next.iter().all(|&word| word == 0) {
+            TickOutcome::Extinct
+        } else if next == self.bits {
+            TickOutcome::Stable
+        } else {
+            TickOutcome::Changed
+        };
+        // the current cells are the spare buffer of the next tick
+        self.spare.0 = std::mem::replace(&mut self.bits, next);
+        outcome
+    }
+
+    /// Word `k` of `row`, with the cells left and right of its cells: (west, same, east).
+    /// Outside a `BoundaryMode::Dead` universe, every cell is dead.
+    fn around(&self, row: &[u64], k: usize) -> [u64; 3] {
+        let wrap = self.boundary == BoundaryMode::Toroidal;
+        let last = row.len() - 1;
+        // bit of the last column in the last word
+        let last_col = (usize::from(self.area.width) - 1) % WORD;
+        let from_left = match k {
+            0 if wrap => row[last] >> last_col & 1,
+            0 => 0,
+            _ => row[k - 1] >> (WORD - 1),
+        };
+        let from_right = match k {
+            k if k < last => row[k + 1] << (WORD - 1),
+            _ if wrap => (row[0] & 1) << last_col,
+            _ => 0,
+        };
+        [row[k] << 1 | from_left, row[k], row[k] >> 1 | from_right]
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn width(&self) -> u16 {
+        self.area.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.area.height
+    }
+}
+
+/// Words a row of `area` takes in `PackedUniverse::bits`
+fn row_words(area: Area) -> usize {
+    usize::from(area.width).div_ceil(WORD)
+}
+
+/// The bits of the last word of a row that are cells in `area`
+fn last_word_mask(area: Area) -> u64 {
+    match usize::from(area.width) % WORD {
+        0 => u64::MAX,
+        n => (1 << n) - 1,
+    }
+}
+
+/// Add `bit` to the 4-bit `counts` of each of the 64 cells, lowest bit first
+fn add_bit(mut counts: [u64; 4], bit: u64) -> [u64; 4] {
+    let mut carry = bit;
+    for count in &mut counts {
+        let next = *count & carry;
+        *count ^= carry;
+        carry = next;
+    }
+    counts
+}
+
+/// Same as for `Universe`
+impl std::fmt::Display for PackedUniverse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let univ = self.to_universe();
+        if f.alternate() {
+            write!(f, "{univ:#}")
+        } else {
+            write!(f, "{univ}")
+        }
+    }
+}
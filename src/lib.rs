@@ -0,0 +1,5 @@
+//! Conway's Game of Life implementation with a TUI
+
+pub mod app;
+/// terminal setup
+pub mod term;
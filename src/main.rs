@@ -1,50 +1,273 @@
-use app::{App, Universe};
-use std::{io::Read, str::FromStr};
+use cgol_tui::{
+    app::{parse_coords, shapes, App, Area, Config, Glyphs, Rule, Universe},
+    term,
+};
+use std::{
+    io::{BufRead, Read, Write},
+    path::PathBuf,
+    str::FromStr,
+};
 
-pub mod app;
+/// Area of the universe in headless mode, unless the pattern is bigger
+const HEADLESS_AREA: Area = Area {
+    width: 64,
+    height: 64,
+};
+
+/// Command line arguments
+struct Args {
+    universes: Vec<Universe>,
+    /// don't use the alternate screen
+    inline: bool,
+    /// export RLE frames into this directory instead of running the TUI
+    export_rle: Option<PathBuf>,
+    /// number of generations to export
+    generations: u64,
+    /// export every `every`th generation
+    every: u64,
+    /// where to dump the board on `d`
+    dump_path: Option<PathBuf>,
+    /// start with the random board from this seed
+    seed: Option<u64>,
+    /// draw cells with these instead of braille dots
+    glyphs: Option<Glyphs>,
+    /// read a generation count from stdin, print the result instead of running the TUI
+    script: bool,
+    /// format to print the result of `script` in
+    emit: Emit,
+    /// experiment setup to start with
+    config: Option<Config>,
+    /// no colors
+    mono: bool,
+    /// rule to use instead of the patterns' own
+    rule: Option<Rule>,
+    /// keep ticking while a menu is open
+    keep_running: bool,
+    /// (`row`;`col`) coordinates of live cells to start with
+    cells: Option<Vec<(u16, u16)>>,
+}
+
+/// Output format of the scripting mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    Rle,
+    Cells,
+}
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rle" => Ok(Emit::Rle),
+            "cells" => Ok(Emit::Cells),
+            _ => Err(format!(
+                "invalid format {s:?}, should be either rle or cells"
+            )),
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let arg_universes = parse_args()?;
+    let args = parse_args()?;
+
+    if let Some(dir) = &args.export_rle {
+        let mut univ = headless_universe(&args)?;
+        let written = univ.export_rle_frames(dir, args.generations, args.every)?;
+        println!("wrote {written} frames to {}", dir.display());
+        return Ok(());
+    }
+    if args.script {
+        let univ = headless_universe(&args)?;
+        return script(
+            univ,
+            args.emit,
+            std::io::stdin().lock(),
+            std::io::stdout().lock(),
+        );
+    }
 
-    let mut app = App::default().with_universes(arg_universes);
+    let mut app = App::default().with_universes(args.universes);
+    if let Some(path) = args.dump_path {
+        app.dump_path = path;
+    }
+    if let Some(config) = args.config {
+        app = app.with_config(config);
+    }
+    if args.glyphs.is_some() {
+        app.glyphs = args.glyphs;
+    }
+    app.mono = args.mono;
+    app.pause_on_menu = !args.keep_running;
+    if let Some(rule) = args.rule {
+        app = app.with_rule(rule);
+    }
+    if let Some(seed) = args.seed {
+        app = app.with_seed(seed);
+    }
 
-    let mut terminal = ratatui::try_init()?;
+    let mut terminal = term::init(args.inline)?;
 
     let res = app.run(&mut terminal);
 
-    ratatui::try_restore()?;
+    term::restore(args.inline)?;
 
     Ok(res?)
 }
 
-fn parse_args() -> Result<Vec<Universe>, Box<dyn std::error::Error>> {
+/// The first pattern in the middle of a `HEADLESS_AREA`, or bigger if needed
+fn headless_universe(args: &Args) -> Result<Universe, Box<dyn std::error::Error>> {
+    let figur = args.universes.first().ok_or("no pattern given")?;
+    let area = Area::new(
+        HEADLESS_AREA.width.max(figur.width()),
+        HEADLESS_AREA.height.max(figur.height()),
+    );
+    Ok(Universe::from_figur(area, figur.clone())?)
+}
+
+/// Read the number of generations from `input`, tick `univ` that many times,
+/// then write the cropped result to `out`
+fn script(
+    mut univ: Universe,
+    emit: Emit,
+    input: impl BufRead,
+    mut out: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let line = input.lines().next().ok_or("no generation count given")??;
+    let generations = line
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| format!("invalid generation count {line:?}: {e}"))?;
+    for _ in 0..generations {
+        univ.tick();
+    }
+    let univ = univ.crop_to_content();
+    match emit {
+        Emit::Rle => write!(out, "{}", univ.to_rle())?,
+        Emit::Cells => write!(out, "{univ:#}")?,
+    }
+    Ok(())
+}
+
+/// A board with only `coords` alive: as big as given in `config`, or just big enough
+fn cells_universe(coords: &[(u16, u16)], config: Option<&Config>) -> Result<Universe, String> {
+    let area = config.and_then(|config| config.area).unwrap_or_else(|| {
+        coords.iter().fold(Area::default(), |area, &(row, col)| {
+            area.union(Area::new(col.saturating_add(1), row.saturating_add(1)))
+        })
+    });
+    Ok(Universe::from_coords(area, coords)?.with_name("cells"))
+}
+
+fn parse_args() -> Result<Args, Box<dyn std::error::Error>> {
     let args = std::env::args().skip(1).collect::<Vec<_>>();
     if args.contains(&"-h".into()) || args.contains(&"--help".into()) {
         println!(
             "A Conway's Game of Life viewer TUI.
-            
-USAGE: cgol-tui [<pattern>,...]
 
-where <pattern> is either a .cells file, or - for stdin"
+USAGE: cgol-tui [OPTIONS] [<pattern>,...]
+
+where <pattern> is either a .cells or .rle file, gzipped with the gzip feature, or - for stdin
+
+OPTIONS:
+    --zoo: tile all the given patterns into one universe
+    --inline: don't use the alternate screen, keep the last frame in the scrollback
+    --mono: no colors, draw cells as blocks with an ASCII border
+    --keep-running: don't pause while typing a command or showing the log
+    --export-rle <DIR>: don't run the TUI, write the first pattern's generations
+                        as RLE files into <DIR>
+    --generations <N>: number of generations to export, default: 100
+    --every <K>: only export every <K>th generation, default: 1
+    --dump <PATH>: append the board to <PATH> on pressing 'd', default: cgol-tui.log in the temp dir
+    --seed <N>: start with the random board from seed <N>, shown in the footer
+    --glyphs <NAME>: draw cells as text instead of braille dots,
+                     one of: squares, circles, blocks, emoji
+    --script: don't run the TUI, read a generation count from stdin,
+              run the first pattern for that long, then print it
+    --emit <rle|cells>: format to print in with --script, default: rle
+    --load <PATH>: load a pattern, erroring if it's invalid
+    --rule <B/S rule>: run the patterns with this rule instead of their own, eg.: B36/S23
+    --shape <NAME>: load a built-in pattern, eg.: glider, gosper glider gun
+    --cells <COORDS>: start with these cells alive, given as space separated <row>,<col> pairs,
+                      eg.: \"0,1 1,2 2,0 2,1 2,2\" for a glider
+    --config <PATH>: start with the size, rule, topology, glyphs and pattern
+                     from a TOML file"
         );
         std::process::exit(0);
     }
-    let piped_universe = {
-        let mut univ = String::new();
-        if args.len() == 1 && args[0] == "-" {
-            std::io::stdin().read_to_string(&mut univ)?;
+
+    let mut zoo = false;
+    let mut parsed = Args {
+        universes: vec![],
+        inline: false,
+        export_rle: None,
+        generations: 100,
+        every: 1,
+        dump_path: None,
+        seed: None,
+        glyphs: None,
+        script: false,
+        emit: Emit::Rle,
+        config: None,
+        mono: false,
+        rule: None,
+        keep_running: false,
+        cells: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--zoo" => zoo = true,
+            "--inline" => parsed.inline = true,
+            "--mono" => parsed.mono = true,
+            "--keep-running" => parsed.keep_running = true,
+            "--export-rle" => parsed.export_rle = Some(value()?.into()),
+            "--generations" => parsed.generations = value()?.parse()?,
+            "--every" => parsed.every = value()?.parse()?,
+            "--dump" => parsed.dump_path = Some(value()?.into()),
+            "--seed" => parsed.seed = Some(value()?.parse()?),
+            "--glyphs" => parsed.glyphs = Some(value()?.parse()?),
+            "--script" => parsed.script = true,
+            "--emit" => parsed.emit = value()?.parse()?,
+            #[cfg(feature = "config")]
+            "--config" => parsed.config = Some(Config::from_file(value()?.as_ref())?),
+            #[cfg(not(feature = "config"))]
+            "--config" => return Err("--config needs the config feature".into()),
+            "--rule" => parsed.rule = Some(Rule::parse(value()?)?),
+            "--shape" => parsed.universes.push(shapes::find(value()?)?),
+            "--cells" => parsed.cells = Some(parse_coords(value()?)?),
+            "--load" => parsed.universes.push(Universe::load(value()?.as_ref())?),
+            "-" => {
+                let mut univ = String::new();
+                std::io::stdin().read_to_string(&mut univ)?;
+                if !univ.is_empty() {
+                    parsed.universes.push(Universe::parse(&univ)?);
+                }
+            }
+            path => {
+                if let Ok(univ) = Universe::load(path.as_ref()) {
+                    parsed.universes.push(univ);
+                }
+            }
         }
-        if univ.is_empty() {
-            vec![]
-        } else {
-            vec![Universe::from_str(&univ)?]
+    }
+
+    if let Some(coords) = &parsed.cells {
+        parsed
+            .universes
+            .push(cells_universe(coords, parsed.config.as_ref())?);
+    }
+    if let Some(rule) = &parsed.rule {
+        for univ in &mut parsed.universes {
+            univ.set_rule(rule.clone());
         }
-    };
-    let universes = args
-        .iter()
-        .flat_map(std::fs::read_to_string)
-        .flat_map(|s| Universe::from_str(&s))
-        .collect::<Vec<_>>();
+    }
+    if zoo && !parsed.universes.is_empty() {
+        parsed.universes = vec![shapes::zoo(&parsed.universes, 2)];
+    }
 
-    Ok([universes, piped_universe].concat())
+    Ok(parsed)
 }
@@ -0,0 +1,86 @@
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, DefaultTerminal, Terminal, TerminalOptions, Viewport};
+use std::io::{self, stdout, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Set up the terminal: raw mode, and unless `inline`, the alternate screen.
+/// In `inline` mode the app is drawn in the normal buffer, so the last frame stays in the scrollback.
+pub fn init(inline: bool) -> io::Result<DefaultTerminal> {
+    set_panic_hook(inline);
+    enable_raw_mode()?;
+    enter(&mut stdout(), inline)?;
+    let viewport = if inline {
+        Viewport::Inline(crossterm::terminal::size()?.1)
+    } else {
+        Viewport::Fullscreen
+    };
+    Terminal::with_options(
+        CrosstermBackend::new(stdout()),
+        TerminalOptions { viewport },
+    )
+}
+
+/// Undo `init`
+pub fn restore(inline: bool) -> io::Result<()> {
+    // disabling raw mode first is important as it has more side effects than leaving the alternate
+    // screen buffer
+    disable_raw_mode()?;
+    leave(&mut stdout(), inline)
+}
+
+fn enter(w: &mut impl Write, inline: bool) -> io::Result<()> {
+    if !inline {
+        execute!(w, EnterAlternateScreen)?;
+    }
+    // clicking toggles cells
+    execute!(w, EnableMouseCapture)
+}
+
+fn leave(w: &mut impl Write, inline: bool) -> io::Result<()> {
+    execute!(w, DisableMouseCapture)?;
+    if inline {
+        // keep the last frame, continue below it
+        writeln!(w)
+    } else {
+        execute!(w, LeaveAlternateScreen)
+    }
+}
+
+/// Copy `text` to the clipboard of the terminal emulator with an OSC 52 escape sequence
+pub fn copy_to_clipboard(w: &mut impl Write, text: &str) -> io::Result<()> {
+    write!(w, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    w.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize].into());
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn set_panic_hook(inline: bool) {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore(inline);
+        hook(info);
+    }));
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+const ENTER_ALT: &[u8] = b"\x1b[?1049h";
+const LEAVE_ALT: &[u8] = b"\x1b[?1049l";
+const ENABLE_MOUSE: &[u8] = b"\x1b[?1000h";
+const DISABLE_MOUSE: &[u8] = b"\x1b[?1000l";
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[test]
+fn alternate_screen() {
+    let mut out = Vec::new();
+    enter(&mut out, false).unwrap();
+    leave(&mut out, false).unwrap();
+    assert!(contains(&out, ENTER_ALT));
+    assert!(contains(&out, LEAVE_ALT));
+}
+
+#[test]
+fn inline() {
+    let mut out = Vec::new();
+    enter(&mut out, true).unwrap();
+    leave(&mut out, true).unwrap();
+    assert!(!contains(&out, ENTER_ALT));
+    assert!(!contains(&out, LEAVE_ALT));
+}
+
+#[test]
+fn mouse_capture() {
+    for inline in [false, true] {
+        let mut out = Vec::new();
+        enter(&mut out, inline).unwrap();
+        leave(&mut out, inline).unwrap();
+        assert!(contains(&out, ENABLE_MOUSE));
+        assert!(contains(&out, DISABLE_MOUSE));
+    }
+}
+
+#[test]
+fn clipboard() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"1234567890"), "MTIzNDU2Nzg5MA==");
+
+    let mut out = Vec::new();
+    copy_to_clipboard(&mut out, "42").unwrap();
+    assert_eq!(out, b"\x1b]52;c;NDI=\x07");
+}
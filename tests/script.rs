@@ -0,0 +1,42 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn run(args: &[&str], stdin: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cgol-tui"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn script() {
+    let path = std::env::temp_dir().join(format!("cgol-tui-script-{}.rle", std::process::id()));
+    std::fs::write(
+        &path,
+        "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n",
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    // a glider looks the same after 4 generations
+    let out = run(&["--script", "--load", path, "--emit", "rle"], "4\n");
+    assert_eq!(out, "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+    let out = run(&["--script", "--load", path, "--emit", "cells"], "2");
+    assert_eq!(out, "!Name: Glider\n..O\nO.O\n.OO\n");
+
+    std::fs::remove_file(path).unwrap();
+}